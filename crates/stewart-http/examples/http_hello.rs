@@ -1,8 +1,8 @@
-use anyhow::Error;
+use anyhow::{Context as _, Error};
 use std::ops::ControlFlow;
 use stewart::{
     sender::{Mailbox, Signal},
-    Actor, Runtime,
+    Actor, ActorError, Context, Id, Runtime,
 };
 use stewart_http::{HttpEvent, RequestAction};
 use stewart_mio::{Registry, RegistryRef};
@@ -15,10 +15,9 @@ fn main() -> Result<(), Error> {
     let registry = Registry::new()?;
 
     // Start the actor
-    let signal = Signal::default();
-    let actor = Service::new(&mut world, signal.clone(), registry.handle())?;
-    let id = world.insert("http-hello", actor);
-    signal.set_id(id);
+    let id = world.create("http-hello")?;
+    let actor = Service::new(&mut world, id, registry.handle())?;
+    world.start(id, actor)?;
 
     // Run the event loop
     stewart_mio::run_event_loop(&mut world, &registry)?;
@@ -31,8 +30,8 @@ struct Service {
 }
 
 impl Service {
-    pub fn new(world: &mut Runtime, signal: Signal, registry: RegistryRef) -> Result<Self, Error> {
-        let http_events = Mailbox::new(signal);
+    pub fn new(world: &mut Runtime, id: Id, registry: RegistryRef) -> Result<Self, Error> {
+        let http_events = world.mailbox(id)?;
 
         let addr = "127.0.0.1:1234".parse()?;
         stewart_http::bind(world, registry, addr, http_events.sender())?;
@@ -43,7 +42,13 @@ impl Service {
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
+    type Message = Signal;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        _signal: Signal,
+    ) -> Result<ControlFlow<()>, ActorError> {
         while let Some(event) = self.http_events.recv() {
             let HttpEvent::Request(request) = event;
 
@@ -53,11 +58,12 @@ impl Actor for Service {
             let body = RESPONSE.into();
             request
                 .actions
-                .send(world, RequestAction::SendResponse(body))
-                .unwrap();
+                .send(ctx, RequestAction::SendResponse(body))
+                .context("failed to send")?
+                .context("failed to send")?;
         }
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 }
 
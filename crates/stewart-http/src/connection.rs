@@ -5,7 +5,7 @@ use anyhow::Error;
 use bytes::{BufMut, Bytes, BytesMut};
 use stewart::{
     sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
+    Actor, ActorError, Context, Id, Runtime, Terminated,
 };
 use stewart_mio::net::tcp;
 use tracing::{event, Level};
@@ -15,39 +15,25 @@ use crate::{
     HttpEvent, HttpHeader, RequestAction, RequestEvent,
 };
 
-pub enum ConnectionEvent {
-    Closed,
-}
-
-pub enum ConnectionAction {
-    Close,
-}
-
-/// Open a TCP based HTTP connection.
+/// Open a TCP based HTTP connection, as a child of `parent`.
 pub fn open(
     world: &mut Runtime,
-    tcp_events: Mailbox<tcp::StreamEvent>,
-    tcp_actions: Sender<tcp::StreamAction>,
-    events: Sender<ConnectionEvent>,
+    parent: Id,
+    tcp: tcp::ConnectedEvent,
     http_events: Sender<HttpEvent>,
-) -> Result<Sender<ConnectionAction>, Error> {
-    let signal = Signal::default();
-    tcp_events.set_signal(signal.clone());
-
-    let actor = Service::new(signal.clone(), tcp_events, tcp_actions, events, http_events);
-    let actions = actor.actions.sender();
+) -> Result<(), Error> {
+    let id = world.create_child(parent, "http-connection")?;
 
-    let id = world.insert("http-connection", actor);
-    signal.set_id(id);
+    let actor = Service::new(world, id, tcp, http_events)?;
+    world.start(id, actor)?;
 
-    Ok(actions)
+    Ok(())
 }
 
 struct Service {
-    signal: Signal,
-    actions: Mailbox<ConnectionAction>,
-    events: Sender<ConnectionEvent>,
+    id: Id,
     tcp_events: Mailbox<tcp::StreamEvent>,
+    tcp_terminated: Mailbox<Terminated>,
     tcp_actions: Sender<tcp::StreamAction>,
     http_events: Sender<HttpEvent>,
 
@@ -63,49 +49,60 @@ enum RequestState {
 
 impl Service {
     pub fn new(
-        signal: Signal,
-        tcp_events: Mailbox<tcp::StreamEvent>,
-        tcp_actions: Sender<tcp::StreamAction>,
-        events: Sender<ConnectionEvent>,
+        world: &mut Runtime,
+        id: Id,
+        tcp: tcp::ConnectedEvent,
         http_events: Sender<HttpEvent>,
-    ) -> Self {
+    ) -> Result<Self, Error> {
         event!(Level::DEBUG, "connection opened");
 
-        let actions = Mailbox::new(signal.clone());
+        // Start receiving events from the TCP stream
+        let tcp_events = world.mailbox(id)?;
+        let action = tcp::StreamAction::Register(tcp_events.sender());
+        tcp.actions.send(world, action)??;
+
+        // The stream may also be removed without sending a closed event, such as when the
+        // listener is removed, so monitor it
+        let tcp_terminated = world.mailbox(id)?;
+        world.monitor(tcp_terminated.sender(), tcp.id)??;
 
-        Self {
-            signal,
-            actions,
-            events,
+        let this = Self {
+            id,
             tcp_events,
-            tcp_actions,
+            tcp_terminated,
+            tcp_actions: tcp.actions,
             http_events,
 
             closed: false,
             parser: HttpParser::default(),
             requests: VecDeque::new(),
-        }
+        };
+        Ok(this)
     }
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        self.process_tcp().unwrap();
+    type Message = Signal;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        _signal: Signal,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        self.process_tcp()?;
 
         // Can't do anything further if we don't have an open TCP connection
         if self.closed {
             event!(Level::DEBUG, "stopping");
 
-            let _ = self.events.send(world, ConnectionEvent::Closed);
-            let _ = self.tcp_actions.send(world, tcp::StreamAction::Close);
+            let _ = self.tcp_actions.send(ctx, tcp::StreamAction::Close);
 
-            return ControlFlow::Break(());
+            return Ok(ControlFlow::Break(()));
         }
 
-        self.process_actions(world).unwrap();
-        self.process_requests(world).unwrap();
+        self.process_requests(ctx)?;
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 }
 
@@ -123,6 +120,11 @@ impl Service {
             }
         }
 
+        if self.tcp_terminated.recv().is_some() {
+            event!(Level::DEBUG, "stream stopped");
+            self.closed = true;
+        }
+
         Ok(())
     }
 
@@ -146,18 +148,6 @@ impl Service {
         }
     }
 
-    fn process_actions(&mut self, world: &mut Runtime) -> Result<(), Error> {
-        while let Some(action) = self.actions.recv() {
-            match action {
-                ConnectionAction::Close => {
-                    self.tcp_actions.send(world, tcp::StreamAction::Close)?;
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     fn process_requests(&mut self, world: &mut Runtime) -> Result<(), Error> {
         // Check new requests we have to send out
         for request in &mut self.requests {
@@ -166,14 +156,14 @@ impl Service {
             };
 
             // Create the mailbox to send a response back through
-            let actions = Mailbox::new(self.signal.clone());
+            let actions = world.mailbox(self.id)?;
 
             // Send the request event
             let event = RequestEvent {
                 header: header.clone(),
                 actions: actions.sender(),
             };
-            self.http_events.send(world, HttpEvent::Request(event))?;
+            self.http_events.send(world, HttpEvent::Request(event))??;
 
             // Continue tracking the request
             *request = RequestState::Pending { actions };
@@ -212,7 +202,7 @@ impl Service {
             data: data.freeze(),
         };
         self.tcp_actions
-            .send(world, tcp::StreamAction::Send(action))?;
+            .send(world, tcp::StreamAction::Send(action))??;
 
         Ok(())
     }
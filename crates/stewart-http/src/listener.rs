@@ -4,7 +4,7 @@ use std::ops::ControlFlow;
 use anyhow::Error;
 use stewart::{
     sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
+    Actor, ActorError, Context, Id, Runtime,
 };
 use stewart_mio::{net::tcp, RegistryRef};
 use tracing::{event, Level};
@@ -18,118 +18,71 @@ pub fn bind(
     addr: SocketAddr,
     http_events: Sender<HttpEvent>,
 ) -> Result<(), Error> {
-    let (actor, signal) = Service::new(world, registry, addr, http_events)?;
+    let id = world.create("http-listener")?;
 
-    let id = world.insert("http-listener", actor);
-    signal.set_id(id);
+    let actor = Service::new(world, id, registry, addr, http_events)?;
+    world.start(id, actor)?;
 
     Ok(())
 }
 
 struct Service {
-    signal: Signal,
     tcp_events: Mailbox<tcp::ListenerEvent>,
-    tcp_actions: Sender<tcp::ListenerAction>,
     http_events: Sender<HttpEvent>,
 
-    connections: Vec<StreamEntry>,
-    closed: bool,
-}
-
-struct StreamEntry {
-    events: Mailbox<connection::ConnectionEvent>,
-    actions: Sender<connection::ConnectionAction>,
     closed: bool,
 }
 
 impl Service {
     fn new(
         world: &mut Runtime,
+        id: Id,
         registry: RegistryRef,
         addr: SocketAddr,
         http_events: Sender<HttpEvent>,
-    ) -> Result<(Self, Signal), Error> {
-        let signal = Signal::default();
-
+    ) -> Result<Self, Error> {
         // Start the listen port
-        let tcp_events = Mailbox::new(signal.clone());
-        let (tcp_actions, server_info) = tcp::bind(world, registry, addr, tcp_events.sender())?;
+        // The TCP listener is a child, so it's closed with this listener, together with its streams
+        let tcp_events = world.mailbox(id)?;
+        let (_, server_info) = tcp::bind(world, id, registry, addr, tcp_events.sender())?;
 
         event!(Level::DEBUG, addr = ?server_info.local_addr, "listening");
 
         let this = Service {
-            signal: signal.clone(),
             tcp_events,
-            tcp_actions,
             http_events,
 
-            connections: Vec::new(),
             closed: false,
         };
-        Ok((this, signal))
+        Ok(this)
     }
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
+    type Message = Signal;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        _signal: Signal,
+    ) -> Result<ControlFlow<()>, ActorError> {
         // Handle incoming TCP connections
         while let Some(event) = self.tcp_events.recv() {
             match event {
                 tcp::ListenerEvent::Connected(event) => {
-                    let events = Mailbox::new(self.signal.clone());
-
-                    let actions = connection::open(
-                        world,
-                        event.events,
-                        event.actions,
-                        events.sender(),
-                        self.http_events.clone(),
-                    )
-                    .unwrap();
-
-                    // Track the connection
-                    let connection = StreamEntry {
-                        events,
-                        actions,
-                        closed: false,
-                    };
-                    self.connections.push(connection);
+                    // Connections are children of the listener, so they're closed with it
+                    let parent = ctx.self_id();
+                    connection::open(ctx, parent, event, self.http_events.clone())?;
                 }
                 tcp::ListenerEvent::Closed => self.closed = true,
             }
         }
 
-        // Process open streams
-        for connection in &mut self.connections {
-            // Handle stream events
-            while let Some(event) = connection.events.recv() {
-                match event {
-                    connection::ConnectionEvent::Closed => {
-                        connection.closed = true;
-                    }
-                }
-            }
-        }
-        self.connections.retain(|s| !s.closed);
-
         if self.closed {
             event!(Level::DEBUG, "stopping");
-
-            let _ = self.tcp_actions.send(world, tcp::ListenerAction::Close);
-
-            // Close all not yet closed streams
-            for stream in &self.connections {
-                if stream.closed {
-                    continue;
-                }
-
-                let _ = stream
-                    .actions
-                    .send(world, connection::ConnectionAction::Close);
-            }
-            return ControlFlow::Break(());
+            return Ok(ControlFlow::Break(()));
         }
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 }
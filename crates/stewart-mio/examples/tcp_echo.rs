@@ -1,10 +1,9 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
 use anyhow::Error;
 use bytes::Bytes;
-use std::ops::ControlFlow;
-use stewart::{
-    sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
-};
+use stewart::{sender::Sender, Actor, ActorError, Context, Id, Runtime};
 use stewart_mio::{
     net::tcp::{self},
     Registry, RegistryRef,
@@ -18,10 +17,9 @@ fn main() -> Result<(), Error> {
     let registry = Registry::new()?;
 
     // Start the actor
-    let signal = Signal::default();
-    let actor = Service::new(&mut world, signal.clone(), registry.handle())?;
-    let id = world.insert("tcp-echo", actor);
-    signal.set_id(id);
+    let id = world.create("tcp-echo")?;
+    let actor = Service::new(&mut world, id, registry.handle())?;
+    world.start(id, actor)?;
 
     // Run the event loop
     stewart_mio::run_event_loop(&mut world, &registry)?;
@@ -30,114 +28,128 @@ fn main() -> Result<(), Error> {
 }
 
 struct Service {
-    signal: Signal,
-    server_mailbox: Mailbox<tcp::ListenerEvent>,
+    sender: Sender<Message>,
     _server_sender: Sender<tcp::ListenerAction>,
-    connections: Vec<Connection>,
+    connections: HashMap<u64, Connection>,
+    next_connection: u64,
+}
+
+enum Message {
+    Listener(tcp::ListenerEvent),
+    Stream(u64, tcp::StreamEvent),
 }
 
 impl Service {
-    pub fn new(world: &mut Runtime, signal: Signal, registry: RegistryRef) -> Result<Self, Error> {
-        let server_mailbox = Mailbox::new(signal.clone());
+    pub fn new(world: &mut Runtime, id: Id, registry: RegistryRef) -> Result<Self, Error> {
+        let sender = Sender::new(id);
 
-        // Start the listen port
+        // Start the listen port, as a child so it's closed with this actor
         let (server_sender, server_info) = tcp::bind(
             world,
+            id,
             registry,
             "127.0.0.1:1234".parse()?,
-            server_mailbox.sender(),
+            sender.clone().map(Message::Listener),
         )?;
         event!(Level::INFO, addr = ?server_info.local_addr, "listening");
 
         let this = Service {
-            signal,
-            server_mailbox,
+            sender,
             _server_sender: server_sender,
-            connections: Vec::new(),
+            connections: HashMap::new(),
+            next_connection: 0,
         };
         Ok(this)
     }
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        self.poll_listener(world)?;
-        self.poll_connections(world).unwrap();
+    type Message = Message;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Message::Listener(event) => match event {
+                tcp::ListenerEvent::Connected(event) => self.on_connected(ctx, event)?,
+                tcp::ListenerEvent::Closed => return Ok(ControlFlow::Break(())),
+            },
+            Message::Stream(key, event) => self.on_stream_event(ctx, key, event)?,
+        }
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 }
 
 impl Service {
-    fn poll_listener(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        while let Some(event) = self.server_mailbox.recv() {
-            match event {
-                tcp::ListenerEvent::Connected(event) => {
-                    event!(Level::INFO, "stream accepted");
-
-                    // Send a greeting message
-                    let data: Bytes = "HELLO WORLD\n".into();
-                    let action = tcp::SendAction { data };
-                    event
-                        .actions
-                        .send(world, tcp::StreamAction::Send(action))
-                        .unwrap();
-
-                    // Keep track of the stream
-                    event.events.set_signal(self.signal.clone());
-                    let connection = Connection {
-                        event,
-                        pending: String::new(),
-                        closed: false,
-                    };
-                    self.connections.push(connection);
-                }
-                tcp::ListenerEvent::Closed => return ControlFlow::Break(()),
-            }
-        }
+    fn on_connected(
+        &mut self,
+        world: &mut Runtime,
+        event: tcp::ConnectedEvent,
+    ) -> Result<(), Error> {
+        event!(Level::INFO, remote_addr = ?event.remote_addr, "stream accepted");
+
+        // Tell the stream where to send its events, tagged so we can find the connection again
+        let key = self.next_connection;
+        self.next_connection += 1;
+
+        let events = self.sender.clone().map(move |e| Message::Stream(key, e));
+        event
+            .actions
+            .send(world, tcp::StreamAction::Register(events))??;
+
+        // Send a greeting message
+        let data: Bytes = "HELLO WORLD\n".into();
+        let action = tcp::SendAction { data };
+        event
+            .actions
+            .send(world, tcp::StreamAction::Send(action))??;
+
+        // Keep track of the stream
+        let connection = Connection {
+            actions: event.actions,
+            pending: String::new(),
+        };
+        self.connections.insert(key, connection);
 
-        ControlFlow::Continue(())
+        Ok(())
     }
 
-    fn poll_connections(&mut self, world: &mut Runtime) -> Result<(), Error> {
-        for connection in &mut self.connections {
-            connection.poll(world)?;
+    fn on_stream_event(
+        &mut self,
+        world: &mut Runtime,
+        key: u64,
+        event: tcp::StreamEvent,
+    ) -> Result<(), Error> {
+        match event {
+            tcp::StreamEvent::Recv(event) => {
+                event!(Level::INFO, bytes = event.data.len(), "received data");
+
+                if let Some(connection) = self.connections.get_mut(&key) {
+                    connection.on_recv(world, event)?;
+                }
+            }
+            tcp::StreamEvent::Closed => {
+                event!(Level::INFO, "stream closed");
+                self.connections.remove(&key);
+            }
         }
 
-        self.connections.retain(|c| !c.closed);
-
         Ok(())
     }
 }
 
 struct Connection {
-    event: tcp::ConnectedEvent,
+    actions: Sender<tcp::StreamAction>,
     pending: String,
-    closed: bool,
 }
 
 impl Connection {
-    fn poll(&mut self, world: &mut Runtime) -> Result<(), Error> {
-        // Handle any incoming TCP stream events
-        while let Some(event) = self.event.events.recv() {
-            match event {
-                tcp::StreamEvent::Recv(event) => {
-                    event!(Level::INFO, bytes = event.data.len(), "received data");
-
-                    let data = std::str::from_utf8(&event.data)?;
-                    self.pending.push_str(data);
-                }
-                tcp::StreamEvent::Closed => {
-                    event!(Level::INFO, "stream closed");
-                    self.closed = true;
-                }
-            }
-        }
-
-        // If the stream is now closed, we can't do anything else
-        if self.closed {
-            return Ok(());
-        }
+    fn on_recv(&mut self, world: &mut Runtime, event: tcp::RecvEvent) -> Result<(), Error> {
+        let data = std::str::from_utf8(&event.data)?;
+        self.pending.push_str(data);
 
         // Check how many messages ended with a newline we have
         let lines: Vec<_> = self.pending.split('\n').collect();
@@ -154,7 +166,7 @@ impl Connection {
 
                 let packet = tcp::SendAction { data: reply.into() };
                 let message = tcp::StreamAction::Send(packet);
-                self.event.actions.send(world, message)?;
+                self.actions.send(world, message)??;
             }
 
             self.pending = remaining;
@@ -2,10 +2,7 @@ use std::net::SocketAddr;
 use std::ops::ControlFlow;
 
 use anyhow::Error;
-use stewart::{
-    sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
-};
+use stewart::{sender::Sender, Actor, ActorError, Context, Runtime};
 use stewart_mio::{net::udp, Registry, RegistryRef};
 use tracing::{event, Level};
 
@@ -16,13 +13,11 @@ fn main() -> Result<(), Error> {
     let registry = Registry::new()?;
 
     // Start the actor
-    let signal = Signal::default();
-    let actor = Service::new(&mut world, signal.clone(), registry.handle())?;
+    let id = world.create("udp-echo")?;
+    let actor = Service::new(&mut world, Sender::new(id), registry.handle())?;
     let server_addr = actor.server_addr;
     let client_send = actor.client_sender.clone();
-
-    let id = world.insert("udp-echo", actor);
-    signal.set_id(id);
+    world.start(id, actor)?;
 
     // Send a message to be echo'd
     let packet = udp::SendAction {
@@ -30,14 +25,14 @@ fn main() -> Result<(), Error> {
         data: "Client Packet".into(),
     };
     let message = udp::Action::Send(packet);
-    client_send.send(&mut world, message)?;
+    client_send.send(&mut world, message)??;
 
     let packet = udp::SendAction {
         remote: server_addr,
         data: "Somewhat Longer Packet".into(),
     };
     let message = udp::Action::Send(packet);
-    client_send.send(&mut world, message)?;
+    client_send.send(&mut world, message)??;
 
     // Run the event loop
     stewart_mio::run_event_loop(&mut world, &registry)?;
@@ -46,43 +41,46 @@ fn main() -> Result<(), Error> {
 }
 
 struct Service {
-    server_mailbox: Mailbox<udp::RecvEvent>,
     server_sender: Sender<udp::Action>,
     server_addr: SocketAddr,
 
-    client_mailbox: Mailbox<udp::RecvEvent>,
     client_sender: Sender<udp::Action>,
 }
 
+enum Message {
+    Server(udp::RecvEvent),
+    Client(udp::RecvEvent),
+}
+
 impl Service {
-    pub fn new(world: &mut Runtime, signal: Signal, registry: RegistryRef) -> Result<Self, Error> {
+    pub fn new(
+        world: &mut Runtime,
+        sender: Sender<Message>,
+        registry: RegistryRef,
+    ) -> Result<Self, Error> {
         // Start the listen port
-        let server_mailbox = Mailbox::new(signal.clone());
         let (server_sender, info) = udp::bind(
             world,
             registry.clone(),
             "0.0.0.0:1234".parse()?,
-            server_mailbox.sender(),
+            sender.clone().map(Message::Server),
         )?;
         event!(Level::INFO, addr = ?info.local_addr, "listening");
         let server_addr = info.local_addr;
 
         // Start the client port
-        let client_mailbox = Mailbox::new(signal);
         let (client_sender, info) = udp::bind(
             world,
             registry.clone(),
             "0.0.0.0:0".parse()?,
-            client_mailbox.sender(),
+            sender.map(Message::Client),
         )?;
         event!(Level::INFO, addr = ?info.local_addr, "sending");
 
         let actor = Service {
-            server_mailbox,
             server_sender,
             server_addr,
 
-            client_mailbox,
             client_sender,
         };
         Ok(actor)
@@ -90,26 +88,47 @@ impl Service {
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        while let Some(packet) = self.server_mailbox.recv() {
-            let data = std::str::from_utf8(&packet.data).unwrap();
-            event!(Level::INFO, data, "server received packet");
-
-            // Echo back with a hello message
-            let data = data.trim();
-            let packet = udp::SendAction {
-                remote: packet.remote,
-                data: format!("Hello, \"{}\"!\n", data).into(),
-            };
-            let message = udp::Action::Send(packet);
-            self.server_sender.send(world, message).unwrap();
+    type Message = Message;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Message::Server(packet) => self.on_server_packet(ctx, packet)?,
+            Message::Client(packet) => self.on_client_packet(packet)?,
         }
 
-        while let Some(packet) = self.client_mailbox.recv() {
-            let data = std::str::from_utf8(&packet.data).unwrap();
-            event!(Level::INFO, data, "client received packet");
-        }
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+impl Service {
+    fn on_server_packet(
+        &mut self,
+        world: &mut Runtime,
+        packet: udp::RecvEvent,
+    ) -> Result<(), Error> {
+        let data = std::str::from_utf8(&packet.data)?;
+        event!(Level::INFO, data, "server received packet");
+
+        // Echo back with a hello message
+        let data = data.trim();
+        let packet = udp::SendAction {
+            remote: packet.remote,
+            data: format!("Hello, \"{}\"!\n", data).into(),
+        };
+        let message = udp::Action::Send(packet);
+        self.server_sender.send(world, message)??;
+
+        Ok(())
+    }
+
+    fn on_client_packet(&mut self, packet: udp::RecvEvent) -> Result<(), Error> {
+        let data = std::str::from_utf8(&packet.data)?;
+        event!(Level::INFO, data, "client received packet");
 
-        ControlFlow::Continue(())
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::Error;
 use mio::{event::Event, Events};
 use stewart::Runtime;
@@ -21,8 +23,16 @@ fn run_poll_loop(world: &mut Runtime, registry: &Registry) -> Result<(), Error>
     let mut events = Events::with_capacity(256);
 
     loop {
-        // Wait for pending events
-        registry.poll(&mut events)?;
+        // Wait for pending events, or until the next timer is due
+        // If processing returned early with work left, only check for events without waiting
+        let timeout = if world.has_pending() {
+            Some(Duration::ZERO)
+        } else {
+            world
+                .next_deadline()
+                .map(|deadline| deadline.saturating_duration_since(world.now()))
+        };
+        registry.poll(&mut events, timeout)?;
 
         // Send out ready events
         for event in events.iter() {
@@ -3,16 +3,18 @@ use std::ops::ControlFlow;
 
 use anyhow::Error;
 use mio::Interest;
-use stewart::{sender::Sender, Actor, Runtime};
+use stewart::{sender::Sender, Actor, ActorError, Context, Id, Runtime, StopReason};
 use tracing::{event, instrument, Level};
 
 use crate::{
     net::{check_io, tcp},
-    ReadyRef, RegistryRef,
+    ReadyRef, ReadyState, RegistryRef,
 };
 
 pub enum ListenerAction {
     /// Close the listener.
+    ///
+    /// This also closes all streams accepted by the listener.
     Close,
 }
 
@@ -26,67 +28,82 @@ pub enum ListenerEvent {
 }
 
 pub struct ConnectedEvent {
-    pub events: Mailbox<tcp::StreamEvent>,
+    pub remote_addr: SocketAddr,
+
+    /// Id of the stream actor, for example to monitor it.
+    pub id: Id,
+
+    /// Sender for actions on the accepted stream.
+    ///
+    /// The stream will not receive data until you send `StreamAction::Register`, telling it where
+    /// to send its events.
     pub actions: Sender<tcp::StreamAction>,
 }
 
-/// Open a TCP stream listener on the given address.
+/// Open a TCP stream listener on the given address, as a child of `parent`.
 ///
 /// TCP, unlike UDP, works with ongoing connections.
 /// Before a connection is established, you first need to 'listen' for those on a port.
+///
+/// Accepted streams are children of the listener, so removing `parent` closes the listener and
+/// all its streams.
 #[instrument("tcp::bind", skip_all)]
 pub fn bind(
     world: &mut Runtime,
+    parent: Id,
     registry: RegistryRef,
     addr: SocketAddr,
     event_sender: Sender<ListenerEvent>,
 ) -> Result<(Sender<ListenerAction>, ListenerInfo), Error> {
-    let (actor, signal, info) = Service::new(registry, addr, event_sender)?;
-    let actions = actor.actions.sender();
+    let id = world.create_child(parent, "tcp-listener")?;
+    let sender = Sender::new(id);
 
-    let id = world.insert("tcp-listener", actor);
-    signal.set_id(id);
+    let (actor, info) = Service::new(registry, sender.clone(), addr, event_sender)?;
+    world.start(id, actor)?;
 
+    let actions = sender.map(Message::Action);
     Ok((actions, info))
 }
 
 struct Service {
     registry: RegistryRef,
-    actions: Mailbox<ListenerAction>,
     events: Sender<ListenerEvent>,
 
     listener: mio::net::TcpListener,
     ready: ReadyRef,
 }
 
+enum Message {
+    Action(ListenerAction),
+    Ready(ReadyState),
+}
+
 impl Service {
     fn new(
         registry: RegistryRef,
+        sender: Sender<Message>,
         addr: SocketAddr,
         events: Sender<ListenerEvent>,
-    ) -> Result<(Self, Signal, ListenerInfo), Error> {
+    ) -> Result<(Self, ListenerInfo), Error> {
         event!(Level::DEBUG, "binding");
 
-        let signal = Signal::default();
-        let actions = Mailbox::new(signal.clone());
-
         // Create the socket
         let mut listener = mio::net::TcpListener::bind(addr)?;
         let local_addr = listener.local_addr()?;
 
         // Register the socket for ready events
-        let ready = registry.register(&mut listener, Interest::READABLE, signal.clone())?;
+        let ready_sender = sender.map(Message::Ready);
+        let ready = registry.register(&mut listener, Interest::READABLE, ready_sender)?;
 
         let this = Self {
             registry,
-            actions,
             events,
 
             listener,
             ready,
         };
         let listener = ListenerInfo { local_addr };
-        Ok((this, signal, listener))
+        Ok((this, listener))
     }
 }
 
@@ -97,43 +114,54 @@ impl Drop for Service {
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        let state = self.ready.take().unwrap();
-
-        if state.readable {
-            self.on_listener_ready(world).unwrap();
+    type Message = Message;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Message::Action(ListenerAction::Close) => {
+                event!(Level::DEBUG, "stopping");
+                return Ok(ControlFlow::Break(()));
+            }
+            Message::Ready(state) => {
+                if state.readable {
+                    self.on_listener_ready(ctx)?;
+                }
+            }
         }
 
-        #[allow(clippy::never_loop)]
-        while let Some(_action) = self.actions.recv() {
-            event!(Level::DEBUG, "stopping");
-            self.events.send(world, ListenerEvent::Closed).unwrap();
-            return ControlFlow::Break(());
-        }
+        Ok(ControlFlow::Continue(()))
+    }
 
-        ControlFlow::Continue(())
+    fn stopping(&mut self, ctx: &mut Context, _reason: StopReason) {
+        if let Err(error) = self.events.send(ctx, ListenerEvent::Closed) {
+            event!(Level::ERROR, ?error, "failed to send closed event");
+        }
     }
 }
 
 impl Service {
-    fn on_listener_ready(&mut self, world: &mut Runtime) -> Result<(), Error> {
+    fn on_listener_ready(&mut self, ctx: &mut Context) -> Result<(), Error> {
         // Accept any pending streams
         while let Some((stream, remote_addr)) = check_io(self.listener.accept())? {
             event!(Level::DEBUG, ?remote_addr, "stream accepted");
 
-            // Start actor
-            let event_mailbox = Mailbox::floating();
-            let actions_sender =
-                tcp::stream::open(world, self.registry.clone(), stream, event_mailbox.sender())?;
+            // Start actor, as a child so it gets closed with the listener
+            let parent = ctx.self_id();
+            let (id, actions) = tcp::stream::open(ctx, parent, self.registry.clone(), stream)?;
 
             // Notify
             // TODO: Temporarily store the stream, until we get a reply truly accepting the stream.
             //  This allows the caller to screen IPs and related data.
             let event = ConnectedEvent {
-                actions: actions_sender,
-                events: event_mailbox,
+                remote_addr,
+                id,
+                actions,
             };
-            self.events.send(world, ListenerEvent::Connected(event))?;
+            self.events.send(ctx, ListenerEvent::Connected(event))??;
         }
 
         Ok(())
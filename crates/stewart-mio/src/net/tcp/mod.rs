@@ -2,6 +2,6 @@ mod listener;
 mod stream;
 
 pub use self::{
-    listener::{bind, ConnectedEvent, ListenerAction, ListenerEvent},
-    stream::{StreamAction, StreamEvent, RecvEvent, SendAction},
+    listener::{bind, ConnectedEvent, ListenerAction, ListenerEvent, ListenerInfo},
+    stream::{RecvEvent, SendAction, StreamAction, StreamEvent},
 };
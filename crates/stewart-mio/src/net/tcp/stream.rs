@@ -7,15 +7,16 @@ use std::{
 use anyhow::Error;
 use bytes::{Buf, Bytes, BytesMut};
 use mio::Interest;
-use stewart::{
-    sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
-};
+use stewart::{sender::Sender, Actor, ActorError, Context, Id, Runtime, StopReason};
 use tracing::{event, Level};
 
-use crate::{ReadyRef, RegistryRef};
+use crate::{ReadyRef, ReadyState, RegistryRef};
 
 pub enum StreamAction {
+    /// Register the sender that will receive the stream's events.
+    ///
+    /// Until a sender is registered, the stream will not read incoming data.
+    Register(Sender<StreamEvent>),
     /// Send a data to the stream.
     Send(SendAction),
     /// Close the stream.
@@ -39,53 +40,52 @@ pub struct RecvEvent {
 
 pub(crate) fn open(
     world: &mut Runtime,
+    parent: Id,
     registry: RegistryRef,
     stream: mio::net::TcpStream,
-    event_sender: Sender<StreamEvent>,
-) -> Result<Sender<StreamAction>, Error> {
-    let signal = Signal::default();
-    let actor = Service::new(signal.clone(), registry, stream, event_sender)?;
-    let actions = actor.actions.sender();
+) -> Result<(Id, Sender<StreamAction>), Error> {
+    let id = world.create_child(parent, "tcp-stream")?;
+    let sender = Sender::new(id);
 
-    let id = world.insert("tcp-stream", actor);
-    signal.set_id(id);
+    let actor = Service::new(registry, sender.clone(), stream)?;
+    world.start(id, actor)?;
 
-    Ok(actions)
+    let actions = sender.map(Message::Action);
+    Ok((id, actions))
 }
 
 struct Service {
-    actions: Mailbox<StreamAction>,
-    events: Sender<StreamEvent>,
+    events: Option<Sender<StreamEvent>>,
 
     stream: mio::net::TcpStream,
     ready: ReadyRef,
-    closed: bool,
 
     queue: VecDeque<Bytes>,
     buffer: BytesMut,
 }
 
+enum Message {
+    Action(StreamAction),
+    Ready(ReadyState),
+}
+
 impl Service {
     fn new(
-        signal: Signal,
         registry: RegistryRef,
+        sender: Sender<Message>,
         mut stream: mio::net::TcpStream,
-        events: Sender<StreamEvent>,
     ) -> Result<Self, Error> {
         event!(Level::DEBUG, "opening stream");
 
-        let actions = Mailbox::new(signal.clone());
-
         // Register for mio events
-        let ready = registry.register(&mut stream, Interest::READABLE, signal)?;
+        let ready_sender = sender.map(Message::Ready);
+        let ready = registry.register(&mut stream, Interest::READABLE, ready_sender)?;
 
         let this = Service {
-            actions,
-            events,
+            events: None,
 
             stream,
             ready,
-            closed: false,
 
             queue: VecDeque::new(),
             buffer: BytesMut::new(),
@@ -101,47 +101,76 @@ impl Drop for Service {
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        self.poll_actions()?;
-        self.poll_ready(world).unwrap();
+    type Message = Message;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        let flow = match message {
+            Message::Action(action) => self.on_action(ctx, action)?,
+            Message::Ready(state) => self.on_ready(ctx, state)?,
+        };
 
-        if self.closed {
-            event!(Level::DEBUG, "stopping");
-            let _ = self.events.send(world, StreamEvent::Closed);
-        }
+        Ok(flow)
+    }
 
-        ControlFlow::Continue(())
+    fn stopping(&mut self, ctx: &mut Context, _reason: StopReason) {
+        // Let the receiver know no more events will come, however the stream stopped
+        if let Some(events) = &self.events {
+            if let Err(error) = events.send(ctx, StreamEvent::Closed) {
+                event!(Level::ERROR, ?error, "failed to send closed event");
+            }
+        }
     }
 }
 
 impl Service {
-    fn poll_actions(&mut self) -> ControlFlow<()> {
-        // Handle actions
-        while let Some(action) = self.actions.recv() {
-            match action {
-                StreamAction::Send(action) => self.on_action_send(action).unwrap(),
-                StreamAction::Close => return ControlFlow::Break(()),
+    fn on_action(
+        &mut self,
+        world: &mut Runtime,
+        action: StreamAction,
+    ) -> Result<ControlFlow<()>, Error> {
+        match action {
+            StreamAction::Register(events) => {
+                self.events = Some(events);
+
+                // We may have missed readable events while not registered, so try reading now
+                return self.on_ready_readable(world);
+            }
+            StreamAction::Send(action) => self.on_action_send(action)?,
+            StreamAction::Close => {
+                event!(Level::DEBUG, "closing stream");
+                return Ok(ControlFlow::Break(()));
             }
         }
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 
-    fn poll_ready(&mut self, world: &mut Runtime) -> Result<(), Error> {
-        let state = self.ready.take()?;
-
-        if state.readable {
-            self.on_ready_readable(world)?;
-        }
-
+    fn on_ready(
+        &mut self,
+        world: &mut Runtime,
+        state: ReadyState,
+    ) -> Result<ControlFlow<()>, Error> {
         if state.writable {
             self.on_ready_writable()?;
         }
 
-        Ok(())
+        if state.readable {
+            return self.on_ready_readable(world);
+        }
+
+        Ok(ControlFlow::Continue(()))
     }
 
-    fn on_ready_readable(&mut self, world: &mut Runtime) -> Result<(), Error> {
+    fn on_ready_readable(&mut self, world: &mut Runtime) -> Result<ControlFlow<()>, Error> {
+        // We can't receive anything until we know where to send it
+        let Some(events) = &self.events else {
+            return Ok(ControlFlow::Continue(()));
+        };
+
         // Make sure we have at least a minimum amount of buffer space left
         if self.buffer.len() < 1024 {
             self.buffer.resize(2048, 0);
@@ -181,15 +210,16 @@ impl Service {
             event!(Level::TRACE, count = bytes_read, "received incoming");
             let data = self.buffer.split_to(bytes_read).freeze();
             let event = RecvEvent { data };
-            self.events.send(world, StreamEvent::Recv(event))?;
+            events.send(world, StreamEvent::Recv(event))??;
         }
 
-        // If the stream got closed, remember
+        // If the stream got closed, stop, which notifies the receiver
         if closed {
-            self.closed = true;
+            event!(Level::DEBUG, "stream closed");
+            return Ok(ControlFlow::Break(()));
         }
 
-        Ok(())
+        Ok(ControlFlow::Continue(()))
     }
 
     fn on_ready_writable(&mut self) -> Result<(), Error> {
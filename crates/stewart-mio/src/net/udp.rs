@@ -4,13 +4,10 @@ use std::{collections::VecDeque, net::SocketAddr, time::Instant};
 use anyhow::Error;
 use bytes::{Bytes, BytesMut};
 use mio::Interest;
-use stewart::{
-    sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
-};
+use stewart::{sender::Sender, Actor, ActorError, Context, Runtime};
 use tracing::{event, instrument, Level};
 
-use crate::{net::check_io, registry::RegistryRef, ReadyRef};
+use crate::{net::check_io, registry::RegistryRef, ReadyRef, ReadyState};
 
 pub enum Action {
     /// Send a packet to a peer.
@@ -41,17 +38,17 @@ pub fn bind(
     addr: SocketAddr,
     event_sender: Sender<RecvEvent>,
 ) -> Result<(Sender<Action>, SocketInfo), Error> {
-    let (actor, signal, info) = Service::new(registry, addr, event_sender)?;
-    let actions = actor.actions.sender();
+    let id = world.create("udp-socket")?;
+    let sender = Sender::new(id);
 
-    let id = world.insert("udp-socket", actor);
-    signal.set_id(id);
+    let (actor, info) = Service::new(registry, sender.clone(), addr, event_sender)?;
+    world.start(id, actor)?;
 
+    let actions = sender.map(Message::Action);
     Ok((actions, info))
 }
 
 struct Service {
-    actions: Mailbox<Action>,
     events: Sender<RecvEvent>,
 
     socket: mio::net::UdpSocket,
@@ -61,26 +58,29 @@ struct Service {
     queue: VecDeque<SendAction>,
 }
 
+enum Message {
+    Action(Action),
+    Ready(ReadyState),
+}
+
 impl Service {
     fn new(
         registry: RegistryRef,
+        sender: Sender<Message>,
         addr: SocketAddr,
         events: Sender<RecvEvent>,
-    ) -> Result<(Self, Signal, SocketInfo), Error> {
+    ) -> Result<(Self, SocketInfo), Error> {
         event!(Level::DEBUG, "binding");
 
-        let signal = Signal::default();
-        let actions = Mailbox::new(signal.clone());
-
         // Create the socket
         let mut socket = mio::net::UdpSocket::bind(addr)?;
         let local_addr = socket.local_addr()?;
 
         // Register the socket for ready events
-        let ready = registry.register(&mut socket, Interest::READABLE, signal.clone())?;
+        let ready_sender = sender.map(Message::Ready);
+        let ready = registry.register(&mut socket, Interest::READABLE, ready_sender)?;
 
         let this = Self {
-            actions,
             events,
 
             socket,
@@ -90,7 +90,7 @@ impl Service {
             queue: VecDeque::new(),
         };
         let info = SocketInfo { local_addr };
-        Ok((this, signal, info))
+        Ok((this, info))
     }
 }
 
@@ -102,26 +102,26 @@ impl Drop for Service {
 }
 
 impl Actor for Service {
-    fn handle(&mut self, world: &mut Runtime) -> ControlFlow<()> {
-        self.poll_actions()?;
-        self.poll_ready(world).unwrap();
+    type Message = Message;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Message::Action(action) => match action {
+                Action::Send(packet) => self.on_action_send(packet)?,
+                Action::Close => return Ok(ControlFlow::Break(())),
+            },
+            Message::Ready(state) => self.on_ready(ctx, state)?,
+        }
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 }
 
 impl Service {
-    fn poll_actions(&mut self) -> ControlFlow<()> {
-        while let Some(message) = self.actions.recv() {
-            match message {
-                Action::Send(packet) => self.on_action_send(packet).unwrap(),
-                Action::Close => return ControlFlow::Break(()),
-            }
-        }
-
-        ControlFlow::Continue(())
-    }
-
     fn on_action_send(&mut self, packet: SendAction) -> Result<(), Error> {
         event!(Level::TRACE, peer = ?packet.remote, "received outgoing packet");
 
@@ -138,9 +138,7 @@ impl Service {
         Ok(())
     }
 
-    fn poll_ready(&mut self, world: &mut Runtime) -> Result<(), Error> {
-        let state = self.ready.take()?;
-
+    fn on_ready(&mut self, world: &mut Runtime, state: ReadyState) -> Result<(), Error> {
         // Handle current state if the socket is ready
         if state.readable {
             self.poll_read(world)?
@@ -184,7 +182,7 @@ impl Service {
             arrived,
             data,
         };
-        self.events.send(world, packet)?;
+        self.events.send(world, packet)??;
 
         Ok(true)
     }
@@ -6,7 +6,7 @@ use std::{
 
 use anyhow::{Context, Error};
 use mio::{event::Source, Events, Interest, Poll, Token};
-use stewart::{sender::Signal, Runtime};
+use stewart::{sender::Sender, Runtime};
 use thunderdome::{Arena, Index};
 use tracing::{event, Level};
 
@@ -38,10 +38,10 @@ impl Registry {
 }
 
 impl Registry {
-    pub(crate) fn poll(&self, events: &mut Events) -> Result<(), Error> {
+    pub(crate) fn poll(&self, events: &mut Events, timeout: Option<Duration>) -> Result<(), Error> {
         let mut inner = self.shared.borrow_mut();
 
-        inner.poll.poll(events, Some(Duration::from_millis(1)))?;
+        inner.poll.poll(events, timeout)?;
 
         Ok(())
     }
@@ -52,21 +52,19 @@ impl Registry {
         token: Token,
         ready: ReadyState,
     ) -> Result<(), Error> {
-        let mut shared = self.shared.borrow_mut();
+        let shared = self.shared.borrow();
 
         let index = Index::from_bits(token.0 as u64).context("invalid token")?;
         let entry = shared
             .tokens
-            .get_mut(index)
+            .get(index)
             .context("failed to get token entry")?;
 
-        entry.signal.send(world)?;
+        // Release the registry before sending, so the message isn't sent while borrowed
+        let sender = entry.sender.clone();
+        drop(shared);
 
-        // Just in case, unhandled read/write events should be combined together if they have not
-        // yet been handled. Mio doesn't garantuee that we'll get another event, and actors should
-        // make sure they handle WouldBlock anyways.
-        entry.state.readable |= ready.readable;
-        entry.state.writable |= ready.writable;
+        sender.send(world, ready)??;
 
         Ok(())
     }
@@ -83,12 +81,14 @@ pub struct RegistryRef {
 impl RegistryRef {
     /// Add a source to the registry, registering it with mio.
     ///
+    /// Ready events for the source will be sent as messages to `sender`.
+    ///
     /// You **must** manually deregister too, see mio docs for more information.
     pub fn register<S>(
         &self,
         source: &mut S,
         interest: Interest,
-        signal: Signal,
+        sender: Sender<ReadyState>,
     ) -> Result<ReadyRef, Error>
     where
         S: Source,
@@ -96,12 +96,8 @@ impl RegistryRef {
         let shared = try_shared(&self.shared)?;
         let mut shared = shared.borrow_mut();
 
-        // Store the ready callback
-        let state = ReadyState {
-            readable: false,
-            writable: false,
-        };
-        let entry = TokenEntry { signal, state };
+        // Store the ready sender
+        let entry = TokenEntry { sender };
         let index = shared.tokens.insert(entry);
 
         // Register with the generated token
@@ -157,25 +153,6 @@ impl ReadyRef {
             event!(Level::ERROR, ?error, "failed to deregister");
         }
     }
-
-    /// Get the ready state, and reset it for future events.
-    pub fn take(&self) -> Result<ReadyState, Error> {
-        let shared = try_shared(&self.shared)?;
-        let mut shared = shared.borrow_mut();
-
-        let entry = shared
-            .tokens
-            .get_mut(self.index)
-            .context("failed to get token entry")?;
-
-        let empty = ReadyState {
-            readable: false,
-            writable: false,
-        };
-        let state = std::mem::replace(&mut entry.state, empty);
-
-        Ok(state)
-    }
 }
 
 struct RegistryShared {
@@ -184,10 +161,13 @@ struct RegistryShared {
 }
 
 struct TokenEntry {
-    signal: Signal,
-    state: ReadyState,
+    sender: Sender<ReadyState>,
 }
 
+/// Ready event of a registered source, sent when mio reports the source as ready.
+///
+/// Mio doesn't guarantee that you'll get another event until you've handled the current one,
+/// so actors should keep handling until they get `WouldBlock`.
 #[derive(Debug, Clone)]
 pub struct ReadyState {
     pub readable: bool,
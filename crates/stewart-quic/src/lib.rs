@@ -7,10 +7,7 @@ use anyhow::Error;
 use bytes::BytesMut;
 use quinn_proto::{DatagramEvent, Endpoint, EndpointConfig, ServerConfig};
 use rustls::{Certificate, PrivateKey};
-use stewart::{
-    sender::{Mailbox, Sender, Signal},
-    Actor, Runtime,
-};
+use stewart::{sender::Sender, Actor, ActorError, Context, Runtime};
 use stewart_mio::{net::udp, RegistryRef};
 use tracing::{event, Level};
 
@@ -21,17 +18,17 @@ pub fn endpoint(
     certificate: Certificate,
     private_key: PrivateKey,
 ) -> Result<(), Error> {
-    let (actor, signal) = Service::new(world, registry, addr, certificate, private_key)?;
+    let id = world.create("quic-endpoint")?;
+    let sender = Sender::new(id);
 
-    let id = world.insert("quic-endpoint", actor);
-    signal.set_id(id);
+    let actor = Service::new(world, sender, registry, addr, certificate, private_key)?;
+    world.start(id, actor)?;
 
     Ok(())
 }
 
 struct Service {
     endpoint: Endpoint,
-    event_mailbox: Mailbox<udp::RecvEvent>,
     #[allow(dead_code)]
     action_sender: Sender<udp::Action>,
 }
@@ -39,11 +36,12 @@ struct Service {
 impl Service {
     fn new(
         world: &mut Runtime,
+        sender: Sender<udp::RecvEvent>,
         registry: RegistryRef,
         addr: SocketAddr,
         certificate: Certificate,
         private_key: PrivateKey,
-    ) -> Result<(Self, Signal), Error> {
+    ) -> Result<Self, Error> {
         // TODO: This is currently always a server, make sure it can be a client
         event!(Level::DEBUG, ?addr, "starting endpoint");
 
@@ -60,47 +58,48 @@ impl Service {
         let endpoint = Endpoint::new(Arc::new(config), Some(Arc::new(server_config)), false);
 
         // Bind the UDP socket to listen on
-        let signal = Signal::default();
-        let event_mailbox = Mailbox::new(signal.clone());
-        let (action_sender, _) = udp::bind(world, registry, addr, event_mailbox.sender())?;
+        let (action_sender, _) = udp::bind(world, registry, addr, sender)?;
 
         let this = Service {
             endpoint,
-            event_mailbox,
             action_sender,
         };
-        Ok((this, signal))
+        Ok(this)
     }
 }
 
 impl Actor for Service {
-    fn handle(&mut self, _world: &mut Runtime) -> ControlFlow<()> {
-        while let Some(packet) = self.event_mailbox.recv() {
-            event!(Level::TRACE, "received packet");
-
-            // TODO: Make this part of the socket API
-            let mut data = BytesMut::with_capacity(packet.data.len());
-            data.extend_from_slice(&packet.data);
-
-            // Pass to the QUIC protocol implementation
-            let result = self
-                .endpoint
-                .handle(packet.arrived, packet.remote, None, None, data);
-
-            if let Some((_connection_handle, event)) = result {
-                match event {
-                    DatagramEvent::ConnectionEvent(event) => {
-                        event!(Level::DEBUG, ?event, "connection event");
-                    }
-                    DatagramEvent::NewConnection(connection) => {
-                        event!(Level::DEBUG, ?connection, "new connection");
-                    }
+    type Message = udp::RecvEvent;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        packet: udp::RecvEvent,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        event!(Level::TRACE, "received packet");
+
+        // TODO: Make this part of the socket API
+        let mut data = BytesMut::with_capacity(packet.data.len());
+        data.extend_from_slice(&packet.data);
+
+        // Pass to the QUIC protocol implementation
+        let result = self
+            .endpoint
+            .handle(packet.arrived, packet.remote, None, None, data);
+
+        if let Some((_connection_handle, event)) = result {
+            match event {
+                DatagramEvent::ConnectionEvent(event) => {
+                    event!(Level::DEBUG, ?event, "connection event");
+                }
+                DatagramEvent::NewConnection(connection) => {
+                    event!(Level::DEBUG, ?connection, "new connection");
                 }
             }
         }
 
         // TODO: Poll transmit
 
-        ControlFlow::Continue(())
+        Ok(ControlFlow::Continue(()))
     }
 }
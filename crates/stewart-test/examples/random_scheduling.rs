@@ -0,0 +1,65 @@
+use std::ops::ControlFlow;
+
+use anyhow::{Context as _, Error};
+use stewart::sender::Sender;
+use stewart::{Actor, ActorError, Context};
+use stewart_test::Harness;
+use tracing::{event, Level};
+
+/// Shows random scheduling finding an ordering assumption that FIFO scheduling always hides.
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    // With FIFO scheduling, the first worker always reports first
+    let order = run(Harness::new())?;
+    event!(Level::INFO, ?order, "fifo");
+
+    // With random scheduling, some seeds break that assumption
+    for seed in 0..16 {
+        let order = run(Harness::with_seed(seed))?;
+
+        if order != ["first", "second"] {
+            event!(Level::INFO, seed, ?order, "found different order");
+        }
+    }
+
+    Ok(())
+}
+
+fn run(mut harness: Harness) -> Result<Vec<&'static str>, Error> {
+    let probe = harness.probe("probe")?;
+
+    for name in ["first", "second"] {
+        let worker = harness.insert(name, Worker { name })?;
+        worker.send(&mut harness, probe.sender())??;
+    }
+    harness.run_until_idle()?;
+
+    let mut order = Vec::new();
+    while let Some(name) = probe.try_message::<&'static str>() {
+        order.push(name);
+    }
+
+    Ok(order)
+}
+
+struct Worker {
+    name: &'static str,
+}
+
+impl Actor for Worker {
+    type Message = Sender<&'static str>;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        report: Sender<&'static str>,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        report
+            .send(ctx, self.name)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
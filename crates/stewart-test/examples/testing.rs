@@ -0,0 +1,70 @@
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use anyhow::{Context as _, Error};
+use stewart::sender::Sender;
+use stewart::{Actor, ActorError, Context};
+use stewart_test::Harness;
+
+/// Walks through testing an actor with the harness, panicking if any expectation fails.
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    let reminder = harness.insert("reminder", Reminder)?;
+    let request = Remind {
+        text: "stand up".to_string(),
+        delay: Duration::from_secs(60),
+        ack: probe.sender(),
+        reply: probe.sender(),
+    };
+    reminder.send(&mut harness, request)??;
+
+    // Step through processing, the acknowledgement comes first
+    while harness.step()? {}
+    probe.expect_message_eq(Ack);
+    probe.expect_no_message();
+
+    // Nothing happens until enough time has passed, without actually waiting
+    harness.advance(Duration::from_secs(59))?;
+    probe.expect_no_message();
+
+    harness.advance(Duration::from_secs(1))?;
+    probe.expect_message_eq("stand up".to_string());
+
+    Ok(())
+}
+
+struct Remind {
+    text: String,
+    delay: Duration,
+    ack: Sender<Ack>,
+    reply: Sender<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Ack;
+
+struct Reminder;
+
+impl Actor for Reminder {
+    type Message = Remind;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Remind,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        message
+            .ack
+            .send(ctx, Ack)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        ctx.send_after(message.reply, message.delay, message.text);
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
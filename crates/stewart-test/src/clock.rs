@@ -0,0 +1,46 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use stewart::Clock;
+
+/// Clock that only moves forward when told to.
+///
+/// Clones share the same time, so a clone can be given to a runtime and advanced from the test.
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl VirtualClock {
+    /// Create a new virtual clock, starting at the current system time.
+    pub fn new() -> Self {
+        Self {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    /// Move the clock forward to `instant`, if it's in the future.
+    pub fn advance_to(&self, instant: Instant) {
+        if instant > self.now.get() {
+            self.now.set(instant);
+        }
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
@@ -0,0 +1,149 @@
+use std::ops::{Deref, DerefMut};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use stewart::{InternalError, ProcessError, Runtime};
+use tracing::{event, Level};
+
+use crate::{Probe, VirtualClock};
+
+/// Runtime wrapper for testing actors deterministically.
+///
+/// Time only passes when the test advances the clock, and messages are only processed when the
+/// test asks for it. `Harness` dereferences to `Runtime`, so it can be used anywhere a
+/// `&mut Runtime` is expected.
+///
+/// When dropped, actors still in the runtime are removed, so tests don't need to clean up.
+pub struct Harness {
+    rt: Runtime,
+    clock: VirtualClock,
+}
+
+impl Harness {
+    /// Create a new harness, with an empty runtime and a virtual clock.
+    pub fn new() -> Self {
+        let clock = VirtualClock::new();
+        let rt = Runtime::builder().clock(clock.clone()).build();
+
+        Self { rt, clock }
+    }
+
+    /// Create a new harness, processing actors in a random order determined by `seed`.
+    ///
+    /// Activations are shuffled too, see `Runtime::set_random_scheduling`.
+    /// If the test panics, the seed is printed, so the failure can be reproduced.
+    pub fn with_seed(seed: u64) -> Self {
+        let mut harness = Self::new();
+        harness.rt.set_random_scheduling(Some(seed), true);
+        harness
+    }
+
+    /// Create a new harness with random scheduling, using a new seed every time.
+    ///
+    /// If the `STEWART_SEED` environment variable is set, it's used as the seed instead.
+    pub fn random() -> Self {
+        let seed = std::env::var("STEWART_SEED")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                now.as_nanos() as u64
+            });
+
+        Self::with_seed(seed)
+    }
+
+    /// Get the virtual clock of the runtime.
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Insert a new probe into the runtime.
+    pub fn probe(&mut self, name: &'static str) -> Result<Probe, InternalError> {
+        Probe::insert(&mut self.rt, name)
+    }
+
+    /// Process one pending message.
+    ///
+    /// Returns true if work is still pending.
+    pub fn step(&mut self) -> Result<bool, ProcessError> {
+        self.rt.process_one()
+    }
+
+    /// Process pending messages until no work is left.
+    ///
+    /// Timers don't fire by themselves, use `advance` to let time pass.
+    pub fn run_until_idle(&mut self) -> Result<(), ProcessError> {
+        while self.rt.process_n(usize::MAX)? {}
+        Ok(())
+    }
+
+    /// Let `duration` pass, firing timers that become due in order, and processing in between.
+    ///
+    /// Timers due at the same time fire together. Periodic timers fire once for every period
+    /// that passes.
+    pub fn advance(&mut self, duration: Duration) -> Result<(), ProcessError> {
+        let target = self.rt.now() + duration;
+        self.run_until_idle()?;
+
+        while let Some(deadline) = self.rt.next_deadline() {
+            if deadline > target {
+                break;
+            }
+
+            self.clock.advance_to(deadline);
+            self.run_until_idle()?;
+        }
+
+        self.clock.advance_to(target);
+        self.run_until_idle()
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Harness {
+    type Target = Runtime;
+
+    fn deref(&self) -> &Runtime {
+        &self.rt
+    }
+}
+
+impl DerefMut for Harness {
+    fn deref_mut(&mut self) -> &mut Runtime {
+        &mut self.rt
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        if let Some(seed) = self.rt.scheduling_seed() {
+            if std::thread::panicking() {
+                eprintln!(
+                    "stewart-test: random scheduling seed {}, set STEWART_SEED to reproduce",
+                    seed
+                );
+            }
+        }
+
+        // Children are removed with their parents
+        let roots: Vec<_> = self
+            .rt
+            .actors()
+            .filter(|info| info.parent.is_none())
+            .map(|info| info.id)
+            .collect();
+
+        for id in roots {
+            if let Err(error) = self.rt.remove(id) {
+                event!(Level::ERROR, ?error, "failed to remove actor");
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+#![deny(missing_docs, unsafe_code)]
+
+//! Deterministic test harness for stewart actors.
+//!
+//! A `Harness` wraps a `Runtime` with a `VirtualClock`, so tests control exactly when messages
+//! are processed and when time passes. `Probe` actors record the messages they get, in order,
+//! for asserting on what your actors send.
+
+mod clock;
+mod harness;
+mod probe;
+
+pub use self::{clock::VirtualClock, harness::Harness, probe::Probe};
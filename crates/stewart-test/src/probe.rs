@@ -0,0 +1,153 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use stewart::sender::Sender;
+use stewart::{Actor, ActorError, Context, Id, InternalError, Runtime};
+
+/// Actor that records every message it gets, for asserting on in tests.
+///
+/// A probe can receive messages of any type, through senders created with `sender`.
+/// Messages are recorded in the order the probe processed them, across all types, so tests can
+/// assert on the order of messages from different sources.
+pub struct Probe {
+    id: Id,
+    received: Rc<RefCell<VecDeque<Recorded>>>,
+}
+
+struct Recorded {
+    type_name: &'static str,
+    message: Box<dyn Any>,
+}
+
+impl Probe {
+    /// Insert a new probe into the runtime.
+    pub fn insert(rt: &mut Runtime, name: &'static str) -> Result<Self, InternalError> {
+        let received = Rc::new(RefCell::new(VecDeque::new()));
+
+        let actor = ProbeActor {
+            received: received.clone(),
+        };
+        let addr = rt.insert(name, actor)?;
+
+        Ok(Self {
+            id: addr.id(),
+            received,
+        })
+    }
+
+    /// Get the `Id` of the probe actor.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Create a sender of messages of type `M` to the probe.
+    pub fn sender<M>(&self) -> Sender<M>
+    where
+        M: 'static,
+    {
+        Sender::new(self.id).map(|message: M| Recorded {
+            type_name: std::any::type_name::<M>(),
+            message: Box::new(message),
+        })
+    }
+
+    /// Get the amount of recorded messages that haven't been taken yet.
+    pub fn len(&self) -> usize {
+        self.received.borrow().len()
+    }
+
+    /// Check if all recorded messages have been taken.
+    pub fn is_empty(&self) -> bool {
+        self.received.borrow().is_empty()
+    }
+
+    /// Take the next recorded message, if it's of type `M`.
+    ///
+    /// Returns `None` if there is no message, or if it's of a different type, leaving it in place.
+    pub fn try_message<M>(&self) -> Option<M>
+    where
+        M: 'static,
+    {
+        let mut received = self.received.borrow_mut();
+
+        if !received.front()?.message.is::<M>() {
+            return None;
+        }
+
+        let recorded = received.pop_front()?;
+        recorded.message.downcast().ok().map(|message| *message)
+    }
+
+    /// Take the next recorded message, which must be of type `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no recorded message, or if it's of a different type.
+    #[track_caller]
+    pub fn expect_message<M>(&self) -> M
+    where
+        M: 'static,
+    {
+        let expected = std::any::type_name::<M>();
+        let recorded = self
+            .received
+            .borrow_mut()
+            .pop_front()
+            .unwrap_or_else(|| panic!("expected message of type {}, got none", expected));
+
+        match recorded.message.downcast() {
+            Ok(message) => *message,
+            Err(_) => panic!(
+                "expected message of type {}, got {}",
+                expected, recorded.type_name
+            ),
+        }
+    }
+
+    /// Take the next recorded message, which must be equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no recorded message, if it's of a different type, or if it's not equal.
+    #[track_caller]
+    pub fn expect_message_eq<M>(&self, expected: M)
+    where
+        M: PartialEq + Debug + 'static,
+    {
+        let message = self.expect_message::<M>();
+        assert_eq!(message, expected, "unexpected message");
+    }
+
+    /// Check that there are no recorded messages left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is a recorded message, naming its type.
+    #[track_caller]
+    pub fn expect_no_message(&self) {
+        if let Some(recorded) = self.received.borrow().front() {
+            panic!("expected no message, got {}", recorded.type_name);
+        }
+    }
+}
+
+struct ProbeActor {
+    received: Rc<RefCell<VecDeque<Recorded>>>,
+}
+
+impl Actor for ProbeActor {
+    type Message = Recorded;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: Recorded,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        self.received.borrow_mut().push_back(message);
+        Ok(ControlFlow::Continue(()))
+    }
+}
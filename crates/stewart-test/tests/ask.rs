@@ -0,0 +1,141 @@
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use anyhow::{Context as _, Error};
+use stewart::sender::{AskError, AskResult, Sender};
+use stewart::{Actor, ActorError, Addr, Context};
+use stewart_test::Harness;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[test]
+fn ask_receives_reply() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let responder = insert_responder(&mut harness, Behavior::Reply)?;
+
+    let request = responder.sender();
+    request.ask(&mut harness, TIMEOUT, probe.sender(), |reply| Request {
+        reply,
+    })??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq::<AskResult<u32>>(Ok(42));
+
+    // The timeout is cancelled, so there's no second message
+    harness.advance(TIMEOUT * 2)?;
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn ask_times_out_without_reply() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let responder = insert_responder(&mut harness, Behavior::Hold)?;
+
+    let request = responder.sender();
+    request.ask(&mut harness, TIMEOUT, probe.sender(), |reply| Request {
+        reply,
+    })??;
+    harness.run_until_idle()?;
+    probe.expect_no_message();
+
+    harness.advance(TIMEOUT / 2)?;
+    probe.expect_no_message();
+
+    harness.advance(TIMEOUT / 2)?;
+    probe.expect_message_eq::<AskResult<u32>>(Err(AskError::Timeout));
+
+    Ok(())
+}
+
+#[test]
+fn ask_is_gone_when_reply_dropped() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let responder = insert_responder(&mut harness, Behavior::Drop)?;
+
+    let request = responder.sender();
+    request.ask(&mut harness, TIMEOUT, probe.sender(), |reply| Request {
+        reply,
+    })??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq::<AskResult<u32>>(Err(AskError::Gone));
+
+    // Only one result is sent, even after the timeout
+    harness.advance(TIMEOUT * 2)?;
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn ask_is_gone_when_target_removed() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let responder = insert_responder(&mut harness, Behavior::Hold)?;
+
+    let request = responder.sender();
+    request.ask(&mut harness, TIMEOUT, probe.sender(), |reply| Request {
+        reply,
+    })??;
+    harness.run_until_idle()?;
+
+    harness.remove(responder.id())??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq::<AskResult<u32>>(Err(AskError::Gone));
+
+    harness.advance(TIMEOUT * 2)?;
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+fn insert_responder(harness: &mut Harness, behavior: Behavior) -> Result<Addr<Responder>, Error> {
+    let actor = Responder {
+        behavior,
+        held: Vec::new(),
+    };
+    let addr = harness.insert("responder", actor)?;
+    Ok(addr)
+}
+
+struct Request {
+    reply: Sender<u32>,
+}
+
+enum Behavior {
+    Reply,
+    Hold,
+    Drop,
+}
+
+struct Responder {
+    behavior: Behavior,
+    held: Vec<Sender<u32>>,
+}
+
+impl Actor for Responder {
+    type Message = Request;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Request,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match self.behavior {
+            Behavior::Reply => {
+                message
+                    .reply
+                    .send(ctx, 42)
+                    .context("failed to send")?
+                    .context("failed to send")?;
+            }
+            Behavior::Hold => self.held.push(message.reply),
+            Behavior::Drop => {}
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -0,0 +1,120 @@
+use std::cell::RefCell;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use anyhow::{Context as _, Error};
+use stewart::{Actor, ActorError, Context, Id, StopReason, Terminated};
+use stewart_test::Harness;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[test]
+fn actor_removing_itself_stops() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let log = Log::default();
+
+    let actor = harness.insert("actor", Tracked::new("actor", &log))?;
+    harness.monitor(probe.sender(), actor.id())??;
+
+    actor.send(&mut harness, actor.id())??;
+    harness.run_until_idle()?;
+
+    assert_eq!(*log.borrow(), ["actor stopping Removed", "actor stopped"]);
+    assert!(harness.actor_info(actor.id()).is_none());
+
+    let terminated = probe.expect_message::<Terminated>();
+    assert_eq!(terminated.id, actor.id());
+    assert_eq!(terminated.reason, StopReason::Removed);
+
+    Ok(())
+}
+
+#[test]
+fn child_removing_parent_stops_both() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let log = Log::default();
+
+    let parent = harness.insert("parent", Tracked::new("parent", &log))?;
+    let child = harness.insert_child(parent.id(), "child", Tracked::new("child", &log))?;
+
+    child.send(&mut harness, parent.id())??;
+    harness.run_until_idle()?;
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "parent stopping Removed",
+            "child stopping Removed",
+            "child stopped",
+            "parent stopped",
+        ]
+    );
+    assert!(harness.actor_info(parent.id()).is_none());
+    assert!(harness.actor_info(child.id()).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn removing_parent_removes_children_in_reverse_order() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let log = Log::default();
+
+    let parent = harness.insert("parent", Tracked::new("parent", &log))?;
+    harness.insert_child(parent.id(), "first", Tracked::new("first", &log))?;
+    harness.insert_child(parent.id(), "second", Tracked::new("second", &log))?;
+
+    harness.remove(parent.id())??;
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "parent stopping Removed",
+            "second stopping Removed",
+            "second stopped",
+            "first stopping Removed",
+            "first stopped",
+            "parent stopped",
+        ]
+    );
+
+    Ok(())
+}
+
+/// Actor that removes the actor it's sent, and logs its lifecycle hooks.
+struct Tracked {
+    name: &'static str,
+    log: Log,
+}
+
+impl Tracked {
+    fn new(name: &'static str, log: &Log) -> Self {
+        Self {
+            name,
+            log: log.clone(),
+        }
+    }
+}
+
+impl Actor for Tracked {
+    type Message = Id;
+
+    fn handle(&mut self, ctx: &mut Context, message: Id) -> Result<ControlFlow<()>, ActorError> {
+        ctx.remove(message)
+            .context("failed to remove")?
+            .context("failed to remove")?;
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn stopping(&mut self, _ctx: &mut Context, reason: StopReason) {
+        let entry = format!("{} stopping {:?}", self.name, reason);
+        self.log.borrow_mut().push(entry);
+    }
+
+    fn stopped(&mut self) {
+        let entry = format!("{} stopped", self.name);
+        self.log.borrow_mut().push(entry);
+    }
+}
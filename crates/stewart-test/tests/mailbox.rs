@@ -0,0 +1,115 @@
+use std::ops::ControlFlow;
+
+use anyhow::{Context as _, Error};
+use stewart::sender::{Mailbox, Sender, Signal};
+use stewart::{Actor, ActorError, Context, SendError};
+use stewart_test::Harness;
+
+#[test]
+fn mailboxes_signal_their_owner() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    let id = harness.create("owner")?;
+    let numbers = harness.mailbox(id)?;
+    let words = harness.mailbox(id)?;
+    assert_eq!(numbers.owner(), id);
+
+    let numbers_sender = numbers.sender();
+    let words_sender = words.sender();
+    let actor = Owner {
+        numbers,
+        words,
+        forward: probe.sender(),
+    };
+    harness.start(id, actor)?;
+
+    numbers_sender.send(&mut harness, 1)??;
+    words_sender.send(&mut harness, "two")??;
+    harness.run_until_idle()?;
+
+    probe.expect_message_eq("1".to_string());
+    probe.expect_message_eq("two".to_string());
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn mailbox_requires_existing_owner() -> Result<(), Error> {
+    let mut harness = Harness::new();
+
+    let id = harness.create("owner")?;
+    harness.remove(id)??;
+
+    assert!(harness.mailbox::<u32>(id).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn failed_signal_leaves_mailbox_empty() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    // The probe can't receive a `Signal`, so signalling it fails
+    let mailbox = harness.mailbox::<u32>(probe.id())?;
+    let result = mailbox.sender().send(&mut harness, 1)?;
+    assert!(matches!(result, Err(SendError::WrongType)));
+    assert!(mailbox.recv().is_none());
+
+    // Once the owner is removed, signalling fails as well
+    harness.remove(probe.id())??;
+    let result = mailbox.sender().send(&mut harness, 2)?;
+    assert!(matches!(result, Err(SendError::NotFound)));
+    assert!(mailbox.recv().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn dropped_mailbox_fails_send() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    let mailbox = harness.mailbox::<u32>(probe.id())?;
+    let sender = mailbox.sender();
+    drop(mailbox);
+
+    let result = sender.send(&mut harness, 1)?;
+    assert!(matches!(result, Err(SendError::NotFound)));
+
+    Ok(())
+}
+
+struct Owner {
+    numbers: Mailbox<u32>,
+    words: Mailbox<&'static str>,
+    forward: Sender<String>,
+}
+
+impl Actor for Owner {
+    type Message = Signal;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        _message: Signal,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        while let Some(number) = self.numbers.recv() {
+            self.forward
+                .send(ctx, number.to_string())
+                .context("failed to send")?
+                .context("failed to send")?;
+        }
+
+        while let Some(word) = self.words.recv() {
+            self.forward
+                .send(ctx, word.to_string())
+                .context("failed to send")?
+                .context("failed to send")?;
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Error;
+use stewart::{ActorOptions, DeadLetter, DeadLetterReason, Overflow, SendError};
+use stewart_test::{Harness, Probe};
+
+#[test]
+fn reject_fails_send_when_full() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (probe, dead_letters) = bounded(&mut harness, Overflow::Reject)?;
+    let sender = probe.sender::<u32>();
+
+    sender.send(&mut harness, 1)??;
+    let result = sender.send(&mut harness, 2)?;
+    assert!(matches!(result, Err(SendError::Full)));
+
+    harness.run_until_idle()?;
+    probe.expect_message_eq(1u32);
+    probe.expect_no_message();
+
+    let letter = dead_letters.expect_message::<DeadLetter>();
+    assert_eq!(letter.target, probe.id());
+    assert_eq!(letter.reason, DeadLetterReason::Full);
+
+    // Once processed there's room again
+    sender.send(&mut harness, 3)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq(3u32);
+
+    Ok(())
+}
+
+#[test]
+fn drop_oldest_keeps_newest_message() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (probe, dead_letters) = bounded(&mut harness, Overflow::DropOldest)?;
+    let sender = probe.sender::<u32>();
+
+    sender.send(&mut harness, 1)??;
+    sender.send(&mut harness, 2)??;
+
+    harness.run_until_idle()?;
+    probe.expect_message_eq(2u32);
+    probe.expect_no_message();
+
+    let letter = dead_letters.expect_message::<DeadLetter>();
+    assert_eq!(letter.reason, DeadLetterReason::Dropped);
+    dead_letters.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn drop_newest_keeps_oldest_message() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (probe, dead_letters) = bounded(&mut harness, Overflow::DropNewest)?;
+    let sender = probe.sender::<u32>();
+
+    sender.send(&mut harness, 1)??;
+    sender.send(&mut harness, 2)??;
+
+    harness.run_until_idle()?;
+    probe.expect_message_eq(1u32);
+    probe.expect_no_message();
+
+    let letter = dead_letters.expect_message::<DeadLetter>();
+    assert_eq!(letter.reason, DeadLetterReason::Dropped);
+    dead_letters.expect_no_message();
+
+    Ok(())
+}
+
+fn bounded(harness: &mut Harness, overflow: Overflow) -> Result<(Probe, Probe), Error> {
+    let probe = harness.probe("probe")?;
+    let options = ActorOptions {
+        capacity: Some(1),
+        overflow,
+    };
+    harness.set_options(probe.id(), options)?;
+
+    let dead_letters = harness.probe("dead-letters")?;
+    harness.set_dead_letters(Some(dead_letters.sender()));
+
+    Ok((probe, dead_letters))
+}
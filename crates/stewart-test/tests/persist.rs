@@ -0,0 +1,222 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Error};
+use stewart::persist::{
+    FileJournal, Journal, PersistContext, PersistOptions, Persistent, PersistentActor,
+};
+use stewart::sender::Sender;
+use stewart::{ActorError, Addr, DecodeError, Snapshot};
+use stewart_test::{Harness, Probe};
+
+const NO_SNAPSHOTS: PersistOptions = PersistOptions {
+    snapshot_every: None,
+};
+
+#[test]
+fn events_recover_after_restart() -> Result<(), Error> {
+    let directory = TempDir::new("recover");
+
+    let mut harness = Harness::new();
+    let tally = insert_tally(&mut harness, &directory, NO_SNAPSHOTS)?;
+    for value in [1, 2, 3] {
+        tally.send(&mut harness, Command::Add(value))??;
+    }
+    harness.run_until_idle()?;
+    drop(harness);
+
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let tally = insert_tally(&mut harness, &directory, NO_SNAPSHOTS)?;
+    assert_eq!(report(&mut harness, &tally, &probe)?, 6);
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_and_later_events_recover() -> Result<(), Error> {
+    let directory = TempDir::new("snapshot");
+    let options = PersistOptions {
+        snapshot_every: Some(2),
+    };
+
+    let mut harness = Harness::new();
+    let tally = insert_tally(&mut harness, &directory, options)?;
+    for value in [1, 2, 3, 4, 5] {
+        tally.send(&mut harness, Command::Add(value))??;
+    }
+    harness.run_until_idle()?;
+    drop(harness);
+
+    // Events up to the last snapshot have been discarded from the journal
+    let contents = FileJournal::open(&directory.0)?.load()?;
+    let (seq, _) = contents.snapshot.context("no snapshot saved")?;
+    assert_eq!(seq, 4);
+    let seqs: Vec<_> = contents.events.iter().map(|(seq, _)| *seq).collect();
+    assert_eq!(seqs, [5]);
+
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let tally = insert_tally(&mut harness, &directory, options)?;
+    assert_eq!(report(&mut harness, &tally, &probe)?, 15);
+
+    Ok(())
+}
+
+#[test]
+fn truncated_write_is_discarded() -> Result<(), Error> {
+    let directory = TempDir::new("truncated");
+
+    let mut harness = Harness::new();
+    let tally = insert_tally(&mut harness, &directory, NO_SNAPSHOTS)?;
+    tally.send(&mut harness, Command::Add(1))??;
+    tally.send(&mut harness, Command::Add(2))??;
+    harness.run_until_idle()?;
+    drop(harness);
+
+    // Simulate the process stopping halfway through writing the third event
+    let mut partial = Vec::new();
+    partial.extend_from_slice(&3u64.to_le_bytes());
+    partial.extend_from_slice(&8u64.to_le_bytes());
+    partial.extend_from_slice(&[4, 0, 0]);
+    append_raw(&directory.0, &partial)?;
+
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let tally = insert_tally(&mut harness, &directory, NO_SNAPSHOTS)?;
+    assert_eq!(report(&mut harness, &tally, &probe)?, 3);
+
+    // New events are appended after the valid ones, not after the partial event
+    tally.send(&mut harness, Command::Add(10))??;
+    harness.run_until_idle()?;
+    drop(harness);
+
+    let contents = FileJournal::open(&directory.0)?.load()?;
+    let seqs: Vec<_> = contents.events.iter().map(|(seq, _)| *seq).collect();
+    assert_eq!(seqs, [1, 2, 3]);
+
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let tally = insert_tally(&mut harness, &directory, NO_SNAPSHOTS)?;
+    assert_eq!(report(&mut harness, &tally, &probe)?, 13);
+
+    Ok(())
+}
+
+#[test]
+fn truncated_header_is_discarded() -> Result<(), Error> {
+    let directory = TempDir::new("header");
+
+    let mut journal = FileJournal::open(&directory.0)?;
+    journal.append(1, b"first")?;
+    append_raw(&directory.0, &[2, 0, 0])?;
+
+    let contents = journal.load()?;
+    assert!(contents.snapshot.is_none());
+    assert_eq!(contents.events, [(1, b"first".to_vec())]);
+
+    journal.append(2, b"second")?;
+    let contents = FileJournal::open(&directory.0)?.load()?;
+    assert_eq!(
+        contents.events,
+        [(1, b"first".to_vec()), (2, b"second".to_vec())]
+    );
+
+    Ok(())
+}
+
+fn insert_tally(
+    harness: &mut Harness,
+    directory: &TempDir,
+    options: PersistOptions,
+) -> Result<Addr<PersistentActor<Tally>>, Error> {
+    let journal = FileJournal::open(&directory.0)?;
+    let actor = PersistentActor::new(Tally { total: 0 }, journal, options);
+    let addr = harness.insert("tally", actor)?;
+    Ok(addr)
+}
+
+fn report(
+    harness: &mut Harness,
+    tally: &Addr<PersistentActor<Tally>>,
+    probe: &Probe,
+) -> Result<u64, Error> {
+    tally.send(harness, Command::Report(probe.sender()))??;
+    harness.run_until_idle()?;
+    Ok(probe.expect_message())
+}
+
+fn append_raw(directory: &Path, bytes: &[u8]) -> Result<(), Error> {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(directory.join("journal.bin"))?;
+    file.write_all(bytes)?;
+    Ok(())
+}
+
+/// Directory removed when dropped, unique to this test process.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let name = format!("stewart-test-{}-{}", std::process::id(), name);
+        let path = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&path);
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+enum Command {
+    Add(u64),
+    Report(Sender<u64>),
+}
+
+struct Tally {
+    total: u64,
+}
+
+impl Snapshot for Tally {
+    fn save(&self, buffer: &mut Vec<u8>) {
+        self.total.save(buffer);
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let total = u64::load(bytes)?;
+        Ok(Self { total })
+    }
+}
+
+impl Persistent for Tally {
+    type Message = Command;
+    type Event = u64;
+
+    fn handle(
+        &mut self,
+        ctx: &mut PersistContext<u64>,
+        command: Command,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match command {
+            Command::Add(value) => ctx.persist(self, value)?,
+            Command::Report(sender) => {
+                sender
+                    .send(ctx, self.total)
+                    .context("failed to send")?
+                    .context("failed to send")?;
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn apply(&mut self, event: &u64) {
+        self.total += event;
+    }
+}
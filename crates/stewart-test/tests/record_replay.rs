@@ -0,0 +1,160 @@
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use anyhow::{Context as _, Error};
+use stewart::sender::Sender;
+use stewart::{
+    Actor, ActorError, Addr, Context, MessageOrigin, ProcessCall, RecordedEvent, Recording,
+    Replayer,
+};
+use stewart_test::{Harness, Probe};
+
+#[test]
+fn replay_reproduces_results() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (probe, counter) = setup(&mut harness)?;
+    harness.record_payloads::<u32>();
+    harness.start_recording();
+
+    counter.send(&mut harness, 3)??;
+    harness.step()?;
+    counter.send(&mut harness, 5)??;
+    counter.send(&mut harness, 7)??;
+    harness.run_until_idle()?;
+
+    let recording = harness.stop_recording().context("not recording")?;
+    let totals = drain(&probe);
+    assert_eq!(totals, [3, 8, 15]);
+
+    // Round trip through bytes, replaying into a runtime set up the same way
+    let recording = Recording::from_bytes(&recording.to_bytes())?;
+    let mut harness = Harness::new();
+    let (probe, _counter) = setup(&mut harness)?;
+
+    let mut replayer = Replayer::new();
+    replayer.register::<u32>();
+    replayer.replay(&mut harness, &recording)?;
+    harness.run_until_idle()?;
+
+    assert_eq!(drain(&probe), totals);
+
+    Ok(())
+}
+
+#[test]
+fn recording_keeps_process_calls() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (_probe, counter) = setup(&mut harness)?;
+    harness.start_recording();
+
+    counter.send(&mut harness, 1)??;
+    harness.step()?;
+    harness.process_n(2)?;
+    harness.process()?;
+
+    let recording = harness.stop_recording().context("not recording")?;
+    let recording = Recording::from_bytes(&recording.to_bytes())?;
+    let calls: Vec<_> = recording
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            RecordedEvent::Process(call) => Some(*call),
+            RecordedEvent::Message(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        [
+            ProcessCall::Count(1),
+            ProcessCall::Count(2),
+            ProcessCall::All
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn only_external_messages_are_replayed() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (probe, counter) = setup(&mut harness)?;
+    schedule(&mut harness, &counter);
+    harness.record_payloads::<u32>();
+    harness.start_recording();
+
+    counter.send(&mut harness, 3)??;
+    harness.run_until_idle()?;
+
+    let recording = harness.stop_recording().context("not recording")?;
+    let totals = drain(&probe);
+    assert_eq!(totals.last(), Some(&103));
+
+    // The counter gets messages from outside and from its timer, and reports to the probe
+    let messages: Vec<_> = recording
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            RecordedEvent::Message(message) => Some((message.target, message.origin)),
+            RecordedEvent::Process(_) => None,
+        })
+        .collect();
+    assert!(messages.contains(&(counter.id(), MessageOrigin::External)));
+    assert!(messages.contains(&(counter.id(), MessageOrigin::Runtime)));
+    assert!(messages
+        .iter()
+        .filter(|(target, _)| *target == probe.id())
+        .all(|(_, origin)| *origin == MessageOrigin::Actor(counter.id())));
+
+    // The timer is set up again, so its message must not be sent a second time by the replay
+    let mut harness = Harness::new();
+    let (probe, counter) = setup(&mut harness)?;
+    schedule(&mut harness, &counter);
+
+    let mut replayer = Replayer::new();
+    replayer.register::<u32>();
+    replayer.replay(&mut harness, &recording)?;
+    harness.run_until_idle()?;
+
+    assert_eq!(drain(&probe), totals);
+
+    Ok(())
+}
+
+fn setup(harness: &mut Harness) -> Result<(Probe, Addr<Counter>), Error> {
+    let probe = harness.probe("probe")?;
+    let counter = Counter {
+        total: 0,
+        report: probe.sender(),
+    };
+    let counter = harness.insert("counter", counter)?;
+
+    Ok((probe, counter))
+}
+
+fn schedule(harness: &mut Harness, counter: &Addr<Counter>) {
+    harness.send_after(counter.sender(), Duration::ZERO, 100);
+}
+
+fn drain(probe: &Probe) -> Vec<u32> {
+    std::iter::from_fn(|| probe.try_message::<u32>()).collect()
+}
+
+struct Counter {
+    total: u32,
+    report: Sender<u32>,
+}
+
+impl Actor for Counter {
+    type Message = u32;
+
+    fn handle(&mut self, ctx: &mut Context, amount: u32) -> Result<ControlFlow<()>, ActorError> {
+        self.total += amount;
+
+        self.report
+            .send(ctx, self.total)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
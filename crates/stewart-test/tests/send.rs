@@ -0,0 +1,51 @@
+use std::ops::ControlFlow;
+
+use anyhow::{Context as _, Error};
+use stewart::sender::Sender;
+use stewart::{Actor, ActorError, Context, DeadLetter, DeadLetterReason, SendError};
+use stewart_test::Harness;
+
+#[test]
+fn send_before_start_fails() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let dead_letters = harness.probe("dead-letters")?;
+    harness.set_dead_letters(Some(dead_letters.sender()));
+
+    let id = harness.create("created")?;
+    let sender = Sender::<u32>::new(id);
+
+    let result = sender.send(&mut harness, 1)?;
+    assert!(matches!(result, Err(SendError::NotStarted)));
+    harness.run_until_idle()?;
+
+    let letter = dead_letters.expect_message::<DeadLetter>();
+    assert_eq!(letter.target, id);
+    assert_eq!(letter.reason, DeadLetterReason::NotStarted);
+    assert_eq!(letter.message.downcast_ref::<u32>(), Some(&1));
+
+    // Once started, the same sender works
+    harness.start(id, Forward { to: probe.sender() })?;
+    sender.send(&mut harness, 2)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq(2u32);
+
+    Ok(())
+}
+
+struct Forward {
+    to: Sender<u32>,
+}
+
+impl Actor for Forward {
+    type Message = u32;
+
+    fn handle(&mut self, ctx: &mut Context, message: u32) -> Result<ControlFlow<()>, ActorError> {
+        self.to
+            .send(ctx, message)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
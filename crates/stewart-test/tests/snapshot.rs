@@ -0,0 +1,181 @@
+use std::ops::ControlFlow;
+
+use anyhow::{Context as _, Error};
+use stewart::{Actor, ActorError, Addr, Context, DecodeError, Key, RuntimeSnapshot, Snapshot};
+use stewart_test::{Harness, Probe};
+
+#[test]
+fn snapshot_round_trip_restores_state_and_pending() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = insert_probe(&mut harness)?;
+    let counter = insert_counter(&mut harness, "counter")?;
+
+    counter.send(&mut harness, Command::Add(2))??;
+    harness.run_until_idle()?;
+
+    // Pending messages are included in the snapshot
+    counter.send(&mut harness, Command::Add(3))??;
+    let bytes = harness.snapshot()?.to_bytes();
+
+    harness.run_until_idle()?;
+    counter.send(&mut harness, Command::Add(100))??;
+    counter.send(&mut harness, Command::Report)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq(105);
+
+    harness.restore(&RuntimeSnapshot::from_bytes(&bytes)?)?;
+    counter.send(&mut harness, Command::Report)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq(5);
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn restore_removes_actors_inserted_after_snapshot() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let _probe = insert_probe(&mut harness)?;
+    let counter = insert_counter(&mut harness, "counter")?;
+
+    let snapshot = harness.snapshot()?;
+
+    let inserted = insert_counter(&mut harness, "inserted")?;
+    let child = harness.create_child(inserted.id(), "child")?;
+
+    harness.restore(&snapshot)?;
+    assert!(harness.actor_info(counter.id()).is_some());
+    assert!(harness.actor_info(inserted.id()).is_none());
+    assert!(harness.actor_info(child).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn restore_recreates_removed_actors() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = insert_probe(&mut harness)?;
+    let counter = insert_counter(&mut harness, "counter")?;
+
+    counter.send(&mut harness, Command::Add(7))??;
+    harness.run_until_idle()?;
+    let bytes = harness.snapshot()?.to_bytes();
+
+    harness.remove(counter.id())??;
+    assert!(harness.actor_info(counter.id()).is_none());
+
+    // The actor comes back with the same id, so the old address still works
+    harness.restore(&RuntimeSnapshot::from_bytes(&bytes)?)?;
+    let info = harness
+        .actor_info(counter.id())
+        .context("counter not recreated")?;
+    assert_eq!(info.name, "counter");
+
+    counter.send(&mut harness, Command::Report)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq(7);
+
+    Ok(())
+}
+
+#[test]
+fn corrupted_snapshot_is_rejected() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = insert_probe(&mut harness)?;
+    let counter = insert_counter(&mut harness, "counter")?;
+
+    counter.send(&mut harness, Command::Add(1))??;
+    harness.run_until_idle()?;
+    let mut bytes = harness.snapshot()?.to_bytes();
+
+    bytes.truncate(bytes.len() - 1);
+    assert!(RuntimeSnapshot::from_bytes(&bytes).is_err());
+
+    // The runtime is unaffected
+
+    counter.send(&mut harness, Command::Add(1))??;
+    counter.send(&mut harness, Command::Report)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq(2);
+
+    Ok(())
+}
+
+fn insert_probe(harness: &mut Harness) -> Result<Probe, Error> {
+    let probe = harness.probe("probe")?;
+    harness.register(probe.id(), "totals", probe.sender::<i32>())?;
+    Ok(probe)
+}
+
+fn insert_counter(harness: &mut Harness, name: &'static str) -> Result<Addr<Counter>, Error> {
+    let counter = harness.insert(name, Counter { total: 0 })?;
+    harness.enable_snapshot(counter)?;
+    Ok(counter)
+}
+
+enum Command {
+    Add(i32),
+    Report,
+}
+
+impl Snapshot for Command {
+    fn save(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Command::Add(value) => {
+                buffer.push(0);
+                value.save(buffer);
+            }
+            Command::Report => buffer.push(1),
+        }
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (tag, rest) = bytes.split_first().ok_or(DecodeError)?;
+
+        match tag {
+            0 => Ok(Command::Add(i32::load(rest)?)),
+            1 => Ok(Command::Report),
+            _ => Err(DecodeError),
+        }
+    }
+}
+
+struct Counter {
+    total: i32,
+}
+
+impl Snapshot for Counter {
+    fn save(&self, buffer: &mut Vec<u8>) {
+        self.total.save(buffer);
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let total = i32::load(bytes)?;
+        Ok(Self { total })
+    }
+}
+
+impl Actor for Counter {
+    type Message = Command;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Command,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Command::Add(value) => self.total += value,
+            Command::Report => {
+                let totals = ctx
+                    .lookup::<i32>(&Key::new("totals"))
+                    .context("failed to find totals")?;
+                totals
+                    .send(ctx, self.total)
+                    .context("failed to send")?
+                    .context("failed to send")?;
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
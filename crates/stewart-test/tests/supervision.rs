@@ -0,0 +1,289 @@
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as _, Error};
+use stewart::sender::Sender;
+use stewart::{
+    Actor, ActorError, Addr, Context, Id, Intensity, StopReason, Strategy, SupervisorOptions,
+};
+use stewart_test::{Harness, Probe};
+
+#[test]
+fn one_for_one_restarts_only_failed_child() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (_, workers, probe) = supervise(&mut harness, Strategy::OneForOne, Intensity::default())?;
+
+    workers[1].send(&mut harness, Fail)??;
+    harness.run_until_idle()?;
+
+    probe.expect_message_eq("b");
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn one_for_all_restarts_all_children() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (_, workers, probe) = supervise(&mut harness, Strategy::OneForAll, Intensity::default())?;
+
+    workers[1].send(&mut harness, Fail)??;
+    harness.run_until_idle()?;
+
+    probe.expect_message_eq("a");
+    probe.expect_message_eq("b");
+    probe.expect_message_eq("c");
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn rest_for_one_restarts_failed_and_later_children() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let (_, workers, probe) = supervise(&mut harness, Strategy::RestForOne, Intensity::default())?;
+
+    workers[1].send(&mut harness, Fail)??;
+    harness.run_until_idle()?;
+
+    probe.expect_message_eq("b");
+    probe.expect_message_eq("c");
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn exceeding_intensity_stops_supervisor() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let intensity = Intensity {
+        max_restarts: 1,
+        period: Duration::from_secs(10),
+    };
+    let (supervisor, workers, probe) = supervise(&mut harness, Strategy::OneForOne, intensity)?;
+
+    workers[0].send(&mut harness, Fail)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq("a");
+
+    workers[0].send(&mut harness, Fail)??;
+    harness.run_until_idle()?;
+    probe.expect_no_message();
+
+    assert!(harness.actor_info(supervisor).is_none());
+    for worker in workers {
+        assert!(harness.actor_info(worker.id()).is_none());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn restarts_outside_period_are_forgotten() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let intensity = Intensity {
+        max_restarts: 1,
+        period: Duration::from_secs(10),
+    };
+    let (supervisor, workers, probe) = supervise(&mut harness, Strategy::OneForOne, intensity)?;
+
+    for _ in 0..3 {
+        workers[0].send(&mut harness, Fail)??;
+        harness.run_until_idle()?;
+        probe.expect_message_eq("a");
+
+        harness.advance(Duration::from_secs(11))?;
+    }
+
+    assert!(harness.actor_info(supervisor).is_some());
+
+    Ok(())
+}
+
+#[test]
+fn running_sibling_restarts_once_returned() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+    let options = SupervisorOptions {
+        strategy: Strategy::OneForAll,
+        intensity: Intensity::default(),
+    };
+    let supervisor = harness.insert_supervisor(None, "supervisor", options)?;
+
+    let events = probe.sender();
+    let mut generation = 0;
+    let spawner = harness.insert_supervised(supervisor, "spawner", move |_, _| {
+        generation += 1;
+        let spawner = Spawner {
+            supervisor,
+            generation,
+            events: events.clone(),
+        };
+        Ok(spawner)
+    })?;
+    harness.run_until_idle()?;
+    probe.expect_message_eq("spawner 1 started".to_string());
+
+    // The new sibling fails to start, restarting the spawner while it's still running
+    spawner.send(&mut harness, Command::Spawn)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq("failing 1 started".to_string());
+    probe.expect_message_eq("failing 2 started".to_string());
+    probe.expect_message_eq("spawner 1 stopping Restart".to_string());
+    probe.expect_message_eq("spawner 2 started".to_string());
+    probe.expect_no_message();
+
+    // The new instance is the one that's kept
+    spawner.send(&mut harness, Command::Ping)??;
+    harness.run_until_idle()?;
+    probe.expect_message_eq("spawner 2 pinged".to_string());
+
+    Ok(())
+}
+
+fn supervise(
+    harness: &mut Harness,
+    strategy: Strategy,
+    intensity: Intensity,
+) -> Result<(Id, Vec<Addr<Worker>>, Probe), Error> {
+    let probe = harness.probe("probe")?;
+    let options = SupervisorOptions {
+        strategy,
+        intensity,
+    };
+    let supervisor = harness.insert_supervisor(None, "supervisor", options)?;
+
+    let mut workers = Vec::new();
+    for name in ["a", "b", "c"] {
+        let started = probe.sender();
+        let worker = harness.insert_supervised(supervisor, name, move |_, _| {
+            let started = started.clone();
+            Ok(Worker { name, started })
+        })?;
+        workers.push(worker);
+    }
+
+    // Skip the first starts, tests only care about restarts
+    harness.run_until_idle()?;
+    for name in ["a", "b", "c"] {
+        probe.expect_message_eq(name);
+    }
+
+    Ok((supervisor, workers, probe))
+}
+
+struct Fail;
+
+struct Worker {
+    name: &'static str,
+    started: Sender<&'static str>,
+}
+
+impl Actor for Worker {
+    type Message = Fail;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        self.started
+            .send(ctx, self.name)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        _message: Fail,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        Err(anyhow!("worker failed").into())
+    }
+}
+
+enum Command {
+    Spawn,
+    Ping,
+}
+
+/// Worker that inserts a sibling that fails to start the first time.
+struct Spawner {
+    supervisor: Id,
+    generation: u32,
+    events: Sender<String>,
+}
+
+impl Spawner {
+    fn event(&self, ctx: &mut Context, what: &str) -> Result<(), Error> {
+        let event = format!("spawner {} {}", self.generation, what);
+        self.events
+            .send(ctx, event)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(())
+    }
+}
+
+impl Actor for Spawner {
+    type Message = Command;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        self.event(ctx, "started")?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Command,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Command::Spawn => {
+                let events = self.events.clone();
+                let mut generation = 0;
+                ctx.insert_supervised(self.supervisor, "failing", move |_, _| {
+                    generation += 1;
+                    let failing = Failing {
+                        generation,
+                        events: events.clone(),
+                    };
+                    Ok(failing)
+                })?;
+            }
+            Command::Ping => self.event(ctx, "pinged")?,
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn stopping(&mut self, ctx: &mut Context, reason: StopReason) {
+        let _ = self.event(ctx, &format!("stopping {:?}", reason));
+    }
+}
+
+struct Failing {
+    generation: u32,
+    events: Sender<String>,
+}
+
+impl Actor for Failing {
+    type Message = ();
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        let event = format!("failing {} started", self.generation);
+        self.events
+            .send(ctx, event)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        if self.generation == 1 {
+            return Err(anyhow!("failed to start").into());
+        }
+
+        Ok(())
+    }
+
+    fn handle(&mut self, _ctx: &mut Context, _message: ()) -> Result<ControlFlow<()>, ActorError> {
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use anyhow::Error;
+use stewart_test::Harness;
+
+#[test]
+fn send_after_delivers_after_delay() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    harness.send_after(probe.sender(), Duration::from_secs(2), 7u32);
+
+    harness.advance(Duration::from_secs(1))?;
+    probe.expect_no_message();
+
+    harness.advance(Duration::from_secs(1))?;
+    probe.expect_message_eq(7u32);
+    assert!(harness.next_deadline().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn cancelled_timer_doesnt_fire() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    let handle = harness.send_after(probe.sender(), Duration::from_secs(1), 7u32);
+    assert!(harness.cancel_timer(handle));
+    assert!(!harness.cancel_timer(handle));
+
+    harness.advance(Duration::from_secs(2))?;
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn interval_fires_every_period() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    let handle = harness.send_interval(probe.sender(), Duration::from_secs(1), 7u32);
+
+    harness.advance(Duration::from_secs(3))?;
+    for _ in 0..3 {
+        probe.expect_message_eq(7u32);
+    }
+    probe.expect_no_message();
+
+    harness.cancel_timer(handle);
+    harness.advance(Duration::from_secs(3))?;
+    probe.expect_no_message();
+
+    Ok(())
+}
+
+#[test]
+fn zero_interval_is_raised_to_one_millisecond() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    harness.send_interval(probe.sender(), Duration::ZERO, 7u32);
+
+    // This would never return if the timer kept firing at the same instant
+    harness.advance(Duration::from_millis(5))?;
+    assert_eq!(probe.len(), 5);
+
+    Ok(())
+}
+
+#[test]
+fn interval_stops_when_target_removed() -> Result<(), Error> {
+    let mut harness = Harness::new();
+    let probe = harness.probe("probe")?;
+
+    harness.send_interval(probe.sender(), Duration::from_secs(1), 7u32);
+    harness.remove(probe.id())??;
+
+    harness.advance(Duration::from_secs(1))?;
+    assert!(harness.next_deadline().is_none());
+
+    Ok(())
+}
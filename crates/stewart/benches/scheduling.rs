@@ -0,0 +1,121 @@
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use stewart::{Actor, ActorError, Addr, Context, Runtime};
+
+/// Amounts of actors in the runtime, scheduling cost should not depend on these.
+const SIZES: &[usize] = &[1_000, 10_000, 100_000];
+
+fn send(c: &mut Criterion) {
+    let mut group = c.benchmark_group("send");
+
+    for &size in SIZES {
+        // Sending to an actor that's already scheduled, with every actor scheduled
+        group.bench_with_input(BenchmarkId::new("queued", size), &size, |b, &size| {
+            let (mut rt, addrs) = setup(size);
+            for addr in &addrs {
+                addr.send(&mut rt, ()).unwrap().unwrap();
+            }
+
+            let target = addrs[size / 2];
+            b.iter(|| target.send(&mut rt, ()).unwrap().unwrap());
+
+            teardown(rt, addrs);
+        });
+
+        // Sending to an idle actor, scheduling it behind up to `size` scheduled actors
+        group.bench_with_input(BenchmarkId::new("enqueue", size), &size, |b, &size| {
+            let (mut rt, addrs) = setup(size);
+            let mut next = 0;
+
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+
+                for _ in 0..iters {
+                    // Once every actor is scheduled, start over, without counting it
+                    if next == size {
+                        rt.process().unwrap();
+                        next = 0;
+                    }
+
+                    let start = Instant::now();
+                    addrs[next].send(&mut rt, ()).unwrap().unwrap();
+                    elapsed += start.elapsed();
+
+                    next += 1;
+                }
+
+                elapsed
+            });
+
+            teardown(rt, addrs);
+        });
+    }
+
+    group.finish();
+}
+
+fn remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("remove");
+
+    for &size in SIZES {
+        // Removing a scheduled actor, with every actor scheduled
+        group.bench_with_input(BenchmarkId::new("queued", size), &size, |b, &size| {
+            let (mut rt, addrs) = setup(size);
+            for addr in &addrs {
+                addr.send(&mut rt, ()).unwrap().unwrap();
+            }
+
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+
+                for _ in 0..iters {
+                    let addr = rt.insert("removed", Idle).unwrap();
+                    addr.send(&mut rt, ()).unwrap().unwrap();
+
+                    let start = Instant::now();
+                    rt.remove(addr.id()).unwrap().unwrap();
+                    elapsed += start.elapsed();
+                }
+
+                elapsed
+            });
+
+            teardown(rt, addrs);
+        });
+    }
+
+    group.finish();
+}
+
+fn setup(size: usize) -> (Runtime, Vec<Addr<Idle>>) {
+    let mut rt = Runtime::default();
+
+    let addrs = (0..size)
+        .map(|_| rt.insert("idle", Idle).unwrap())
+        .collect();
+
+    (rt, addrs)
+}
+
+fn teardown(mut rt: Runtime, addrs: Vec<Addr<Idle>>) {
+    rt.process().unwrap();
+
+    for addr in addrs {
+        rt.remove(addr.id()).unwrap().unwrap();
+    }
+}
+
+struct Idle;
+
+impl Actor for Idle {
+    type Message = ();
+
+    fn handle(&mut self, _ctx: &mut Context, _message: ()) -> Result<ControlFlow<()>, ActorError> {
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+criterion_group!(benches, send, remove);
+criterion_main!(benches);
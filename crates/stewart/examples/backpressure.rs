@@ -0,0 +1,94 @@
+use anyhow::Error;
+use std::ops::ControlFlow;
+use stewart::{
+    Actor, ActorError, ActorOptions, Addr, CapacityAvailable, Context, Overflow, Runtime, SendError,
+};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+
+    // The consumer can only hold a few pending messages at a time
+    let options = ActorOptions {
+        capacity: Some(4),
+        overflow: Overflow::Reject,
+    };
+    let consumer = rt.insert_with("consumer", Consumer, options)?;
+
+    let producer = Producer {
+        consumer,
+        next: 0,
+        count: 16,
+    };
+    rt.insert("producer", producer)?;
+
+    rt.process()?;
+
+    rt.remove(consumer.id())??;
+
+    Ok(())
+}
+
+struct Producer {
+    consumer: Addr<Consumer>,
+    next: usize,
+    count: usize,
+}
+
+impl Producer {
+    /// Send as much as we can, until the consumer is full.
+    fn produce(&mut self, ctx: &mut Context) -> Result<ControlFlow<()>, Error> {
+        while self.next < self.count {
+            match self.consumer.send(ctx, self.next)? {
+                Ok(()) => self.next += 1,
+                Err(SendError::Full) => {
+                    // Wait until the consumer has room again
+                    event!(Level::INFO, next = self.next, "consumer full, waiting");
+                    let watcher = ctx.self_sender();
+                    ctx.notify_capacity(watcher, self.consumer.id())??;
+                    return Ok(ControlFlow::Continue(()));
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        event!(Level::INFO, "done producing");
+        Ok(ControlFlow::Break(()))
+    }
+}
+
+impl Actor for Producer {
+    type Message = CapacityAvailable;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        // Send what fits, the rest is sent as the consumer makes room
+        let _ = self.produce(ctx)?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        _message: CapacityAvailable,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        let flow = self.produce(ctx)?;
+        Ok(flow)
+    }
+}
+
+struct Consumer;
+
+impl Actor for Consumer {
+    type Message = usize;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: usize,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        event!(Level::INFO, message, "consumed");
+        Ok(ControlFlow::Continue(()))
+    }
+}
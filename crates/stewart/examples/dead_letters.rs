@@ -0,0 +1,75 @@
+use anyhow::Error;
+use std::ops::ControlFlow;
+use stewart::{Actor, ActorError, Context, DeadLetter, Runtime};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+
+    // Catch anything that can't be delivered
+    let sink = rt.insert("dead-letters", Sink)?;
+    rt.set_dead_letters(Some(sink.sender()));
+
+    let addr = rt.insert("echo", Echo)?;
+    let id = addr.id();
+
+    // Sending by untyped id can send the wrong type
+    let _ = rt.send(id, 42u32)?;
+
+    // Messages still pending when an actor is removed aren't delivered
+    addr.send(&mut rt, "lost".to_string())??;
+    rt.remove(id)??;
+
+    // And neither are messages sent after
+    let _ = addr.send(&mut rt, "too late".to_string())?;
+
+    rt.process()?;
+
+    rt.set_dead_letters(None);
+    rt.remove(sink.id())??;
+
+    Ok(())
+}
+
+struct Sink;
+
+impl Actor for Sink {
+    type Message = DeadLetter;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        letter: DeadLetter,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        // The message can be downcast to inspect it
+        let text = letter.message.downcast_ref::<String>();
+
+        event!(
+            Level::WARN,
+            target = ?letter.target,
+            type_name = letter.type_name,
+            reason = ?letter.reason,
+            ?text,
+            "dead letter",
+        );
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+struct Echo;
+
+impl Actor for Echo {
+    type Message = String;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: String,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        event!(Level::INFO, message, "echo");
+        Ok(ControlFlow::Continue(()))
+    }
+}
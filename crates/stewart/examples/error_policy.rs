@@ -0,0 +1,62 @@
+use anyhow::{anyhow, Error};
+use std::ops::ControlFlow;
+use stewart::{Actor, ActorError, Context, ErrorPolicy, Runtime};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    // Keep actors around when they fail, they don't hold any state that could be corrupted
+    let mut rt = Runtime::builder()
+        .actor_errors(ErrorPolicy::Continue)
+        .build();
+
+    let addr = rt.insert("parser", Parser { parsed: 0 })?;
+    for input in ["1", "two", "3"] {
+        addr.send(&mut rt, input.to_string())??;
+    }
+    rt.process()?;
+    rt.remove(addr.id())??;
+
+    // Or let the caller decide what to do, with details on what failed
+    let mut rt = Runtime::builder().actor_errors(ErrorPolicy::Abort).build();
+
+    let addr = rt.insert("parser", Parser { parsed: 0 })?;
+    addr.send(&mut rt, "four".to_string())??;
+    if let Err(error) = rt.process() {
+        let (name, kind) = (error.name(), error.kind());
+        event!(
+            Level::WARN,
+            name,
+            ?kind,
+            "aborted processing: {:#}",
+            Error::from(error)
+        );
+    }
+    rt.remove(addr.id())??;
+
+    Ok(())
+}
+
+struct Parser {
+    parsed: usize,
+}
+
+impl Actor for Parser {
+    type Message = String;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: String,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        let value: u32 = message
+            .parse()
+            .map_err(|_| anyhow!("invalid number {:?}", message))?;
+
+        self.parsed += 1;
+        event!(Level::INFO, value, parsed = self.parsed, "parsed");
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Error;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+use stewart::{Actor, ActorError, Context, Runtime};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+
+    // Queue up more work than fits in one frame
+    let addr = rt.insert("worker", Worker { processed: 0 })?;
+    for _ in 0..200 {
+        addr.send(&mut rt, ())??;
+    }
+
+    let frame_time = Duration::from_millis(16);
+    let mut frame = 0;
+
+    loop {
+        let frame_start = Instant::now();
+        frame += 1;
+
+        // Give actors part of the frame, leaving the rest for simulation and rendering
+        let pending = rt.process_for(Duration::from_millis(4))?;
+        event!(Level::INFO, frame, pending, "frame");
+
+        if !pending {
+            break;
+        }
+
+        std::thread::sleep(frame_time.saturating_sub(frame_start.elapsed()));
+    }
+
+    // Find out where the time went
+    for info in rt.actors() {
+        event!(
+            Level::INFO,
+            name = info.name,
+            processed = info.stats.processed,
+            handle_time = ?info.stats.handle_time,
+            "actor stats",
+        );
+    }
+
+    rt.remove(addr.id())??;
+
+    Ok(())
+}
+
+struct Worker {
+    processed: usize,
+}
+
+impl Actor for Worker {
+    type Message = ();
+
+    fn handle(&mut self, _ctx: &mut Context, _message: ()) -> Result<ControlFlow<()>, ActorError> {
+        // Pretend to do some expensive work
+        std::thread::sleep(Duration::from_micros(100));
+        self.processed += 1;
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
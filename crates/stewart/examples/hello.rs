@@ -1,7 +1,8 @@
-use anyhow::Error;
+use anyhow::{Context as _, Error};
 use std::ops::ControlFlow;
-use stewart::sender::Sender;
-use stewart::{Actor, ActorError, Runtime};
+use std::time::Duration;
+use stewart::sender::{AskResult, Sender};
+use stewart::{Actor, ActorError, Context, Runtime};
 use tracing::{event, Level};
 use uuid::Uuid;
 
@@ -16,40 +17,12 @@ fn main() -> Result<(), Error> {
     // Start the hello service
     let service_sender = hello_service::start(&mut rt, "Example".to_string())?;
 
-    // Start a result receiver, so we can actually receive the replies given
-    let id = rt.insert("result-listener", ResultListener)?;
-    let result_sender = Sender::new(id);
-
-    // Now that we have an address, send it some data
-    event!(Level::INFO, "sending messages");
-
-    let action = hello::Action::Greet {
-        name: "World".to_string(),
-    };
-    let message = hello::Request {
-        id: Uuid::new_v4(),
-        action,
-        result_sender: result_sender.clone(),
-    };
-    service_sender.send(&mut rt, message)??;
-
-    let action = hello::Action::Greet {
-        name: "Actors".to_string(),
-    };
-    let message = hello::Request {
-        id: Uuid::new_v4(),
-        action,
-        result_sender: result_sender.clone(),
+    // Start a client, which will talk to the service when started
+    let client = Client {
+        service: service_sender,
+        pending: 0,
     };
-    service_sender.send(&mut rt, message)??;
-
-    // Stop the actor
-    let message = hello::Request {
-        id: Uuid::new_v4(),
-        action: hello::Action::Stop,
-        result_sender: result_sender.clone(),
-    };
-    service_sender.send(&mut rt, message)??;
+    rt.insert("client", client)?;
 
     // Process messages
     rt.process()?;
@@ -57,17 +30,70 @@ fn main() -> Result<(), Error> {
     Ok(())
 }
 
-struct ResultListener;
+struct Client {
+    service: Sender<hello::Request>,
+    pending: usize,
+}
+
+impl Client {
+    fn request(&mut self, ctx: &mut Context, action: hello::Action) -> Result<(), Error> {
+        // Ask the service, it gets a one-shot sender to reply with, and we receive the reply on
+        // our own sender from the context
+        // If the service doesn't reply in time, or drops the request, we receive an error instead
+        let reply_to = ctx.self_sender();
+        let timeout = Duration::from_secs(1);
+        self.service
+            .ask(ctx, timeout, reply_to, |result_sender| hello::Request {
+                id: Uuid::new_v4(),
+                action,
+                result_sender,
+            })
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        self.pending += 1;
+        Ok(())
+    }
+}
+
+impl Actor for Client {
+    type Message = AskResult<Uuid>;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        event!(Level::INFO, "sending messages");
 
-impl Actor for ResultListener {
-    type Message = Uuid;
+        let action = hello::Action::Greet {
+            name: "World".to_string(),
+        };
+        self.request(ctx, action)?;
+
+        let action = hello::Action::Greet {
+            name: "Actors".to_string(),
+        };
+        self.request(ctx, action)?;
+
+        // Stop the service
+        self.request(ctx, hello::Action::Stop)?;
+
+        Ok(())
+    }
 
     fn handle(
         &mut self,
-        _rt: &mut Runtime,
+        _ctx: &mut Context,
         message: Self::Message,
     ) -> Result<ControlFlow<()>, ActorError> {
-        event!(Level::INFO, uuid = ?message, "received response");
+        match message {
+            Ok(uuid) => event!(Level::INFO, ?uuid, "received response"),
+            Err(error) => event!(Level::WARN, ?error, "no response"),
+        }
+
+        // Stop once we've received all replies
+        self.pending -= 1;
+        if self.pending == 0 {
+            return Ok(ControlFlow::Break(()));
+        }
+
         Ok(ControlFlow::Continue(()))
     }
 }
@@ -76,7 +102,7 @@ impl Actor for ResultListener {
 mod hello_service {
     use anyhow::{Context, Error};
     use std::ops::ControlFlow;
-    use stewart::{sender::Sender, Actor, ActorError, Runtime};
+    use stewart::{sender::Sender, Actor, ActorError, Context as ActorContext, Runtime};
     use tracing::{event, instrument, Level};
 
     /// You can define your public interfaces as a "protocol", which contains just the types
@@ -116,12 +142,12 @@ mod hello_service {
         event!(Level::INFO, "starting");
 
         let service = Service::new(name);
-        let id = rt.insert("hello", service)?;
+        let addr = rt.insert("hello", service)?;
 
         // Send messages to your actor using a `Sender` abstraction.
-        // You can send directly using the runtime, but using a sender will let you add mapping to
-        // separate public from private messages.
-        let sender = Sender::new(id);
+        // You can send directly using the typed address, but using a sender will let you add
+        // mapping to separate public from private messages.
+        let sender = addr.sender();
 
         Ok(sender)
     }
@@ -144,7 +170,7 @@ mod hello_service {
 
         fn handle(
             &mut self,
-            rt: &mut Runtime,
+            ctx: &mut ActorContext,
             message: protocol::Request,
         ) -> Result<ControlFlow<()>, ActorError> {
             event!(Level::INFO, "processing messages");
@@ -165,7 +191,7 @@ mod hello_service {
             // Reply back to the sender.
             message
                 .result_sender
-                .send(rt, message.id)
+                .send(ctx, message.id)
                 .context("failed to send")?
                 .context("failed to send")?;
 
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Error};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+use std::time::Duration;
+use stewart::{Actor, ActorError, Context, ErrorPolicy, Id, Runtime, RuntimeObserver, StopReason};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    // The observer is owned by the runtime, so share the results to read them afterwards
+    let metrics = Rc::new(RefCell::new(Metrics::default()));
+    let mut rt = Runtime::builder()
+        .actor_errors(ErrorPolicy::Continue)
+        .observer(MetricsObserver(metrics.clone()))
+        .build();
+
+    let addr = rt.insert("squarer", Squarer)?;
+    for value in [1, 2, 3, 0] {
+        addr.send(&mut rt, value)??;
+    }
+    rt.process()?;
+    rt.remove(addr.id())??;
+
+    let metrics = metrics.borrow();
+    event!(Level::INFO, ?metrics, "collected metrics");
+
+    Ok(())
+}
+
+#[derive(Default, Debug)]
+struct Metrics {
+    sent: HashMap<&'static str, usize>,
+    processed: usize,
+    busy: Duration,
+    errors: usize,
+}
+
+struct MetricsObserver(Rc<RefCell<Metrics>>);
+
+impl RuntimeObserver for MetricsObserver {
+    fn removed(&mut self, _id: Id, name: &'static str, reason: StopReason) {
+        event!(Level::INFO, name, ?reason, "observed removal");
+    }
+
+    fn sent(&mut self, _target: Id, type_name: &'static str, _message: &dyn Any) {
+        *self.0.borrow_mut().sent.entry(type_name).or_default() += 1;
+    }
+
+    fn activation_ended(&mut self, _id: Id, _name: &'static str, processed: usize, time: Duration) {
+        let mut metrics = self.0.borrow_mut();
+        metrics.processed += processed;
+        metrics.busy += time;
+    }
+
+    fn actor_failed(&mut self, _id: Id, _name: Option<&'static str>, _error: &ActorError) {
+        self.0.borrow_mut().errors += 1;
+    }
+}
+
+struct Squarer;
+
+impl Actor for Squarer {
+    type Message = u32;
+
+    fn handle(&mut self, _ctx: &mut Context, value: u32) -> Result<ControlFlow<()>, ActorError> {
+        if value == 0 {
+            return Err(anyhow!("nothing to square").into());
+        }
+
+        event!(Level::INFO, square = value * value, "squared");
+        Ok(ControlFlow::Continue(()))
+    }
+}
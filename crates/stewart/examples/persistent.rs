@@ -0,0 +1,133 @@
+use anyhow::Error;
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
+use stewart::persist::{FileJournal, PersistContext, PersistOptions, Persistent, PersistentActor};
+use stewart::{ActorError, Context, DecodeError, Runtime, Snapshot};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let directory = std::env::temp_dir().join("stewart-persistent-example");
+    let _ = std::fs::remove_dir_all(&directory);
+
+    // Snapshot often, so this example shows both snapshots and events being recovered
+    let options = PersistOptions {
+        snapshot_every: Some(2),
+    };
+
+    // First run, opening a few sessions
+    let mut rt = Runtime::default();
+    let journal = FileJournal::open(&directory)?;
+    let sessions = PersistentActor::new(Sessions::default(), journal, options);
+    let sessions = rt.insert("sessions", sessions)?;
+
+    sessions.send(&mut rt, Command::Open("alice".to_string()))??;
+    sessions.send(&mut rt, Command::Open("bob".to_string()))??;
+    sessions.send(&mut rt, Command::Open("carol".to_string()))??;
+    sessions.send(&mut rt, Command::Close("alice".to_string()))??;
+    sessions.send(&mut rt, Command::Open("dave".to_string()))??;
+    rt.process()?;
+    rt.remove(sessions.id())??;
+    drop(rt);
+
+    // Second run, as if the process restarted, the sessions are recovered from the journal
+    let mut rt = Runtime::default();
+    let journal = FileJournal::open(&directory)?;
+    let sessions = PersistentActor::new(Sessions::default(), journal, options);
+    let sessions = rt.insert("sessions", sessions)?;
+
+    sessions.send(&mut rt, Command::List)??;
+    rt.process()?;
+    rt.remove(sessions.id())??;
+
+    std::fs::remove_dir_all(&directory)?;
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Sessions {
+    open: BTreeSet<String>,
+}
+
+impl Snapshot for Sessions {
+    fn save(&self, buffer: &mut Vec<u8>) {
+        let names: Vec<_> = self.open.iter().map(String::as_str).collect();
+        names.join("\n").save(buffer);
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let names = String::load(bytes)?;
+        let open = names.lines().map(str::to_string).collect();
+        Ok(Self { open })
+    }
+}
+
+impl Persistent for Sessions {
+    type Message = Command;
+    type Event = SessionEvent;
+
+    fn recovered(&mut self, _ctx: &mut Context) -> Result<(), ActorError> {
+        event!(Level::INFO, count = self.open.len(), "recovered sessions");
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        ctx: &mut PersistContext<SessionEvent>,
+        command: Command,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match command {
+            Command::Open(name) => ctx.persist(self, SessionEvent::Opened(name))?,
+            Command::Close(name) => ctx.persist(self, SessionEvent::Closed(name))?,
+            Command::List => event!(Level::INFO, sessions = ?self.open, "open sessions"),
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn apply(&mut self, event: &SessionEvent) {
+        match event {
+            SessionEvent::Opened(name) => {
+                self.open.insert(name.clone());
+            }
+            SessionEvent::Closed(name) => {
+                self.open.remove(name);
+            }
+        }
+    }
+}
+
+enum Command {
+    Open(String),
+    Close(String),
+    List,
+}
+
+enum SessionEvent {
+    Opened(String),
+    Closed(String),
+}
+
+impl Snapshot for SessionEvent {
+    fn save(&self, buffer: &mut Vec<u8>) {
+        let (tag, name) = match self {
+            SessionEvent::Opened(name) => (0u8, name),
+            SessionEvent::Closed(name) => (1u8, name),
+        };
+        buffer.push(tag);
+        name.save(buffer);
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (tag, name) = bytes.split_first().ok_or(DecodeError)?;
+        let name = String::load(name)?;
+
+        match tag {
+            0 => Ok(SessionEvent::Opened(name)),
+            1 => Ok(SessionEvent::Closed(name)),
+            _ => Err(DecodeError),
+        }
+    }
+}
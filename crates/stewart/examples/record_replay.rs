@@ -0,0 +1,90 @@
+use anyhow::{Context as _, Error};
+use std::ops::ControlFlow;
+use stewart::sender::Sender;
+use stewart::{Actor, ActorError, Addr, Context, Recording, Replayer, Runtime};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    // Record a session, opting in to recording payloads of the messages we send in
+    let mut rt = Runtime::default();
+    rt.record_payloads::<u32>();
+    rt.start_recording();
+
+    let (logger, counter) = setup(&mut rt)?;
+    for amount in [3, 5, 7] {
+        counter.send(&mut rt, amount)??;
+        rt.process()?;
+    }
+
+    let recording = rt.stop_recording().context("not recording")?;
+    event!(Level::INFO, events = recording.events.len(), "recorded");
+    teardown(&mut rt, logger, counter)?;
+
+    // Recordings can be stored, for example in a file, and loaded again later
+    let bytes = recording.to_bytes();
+    let recording = Recording::from_bytes(&bytes)?;
+
+    // Replay into a fresh runtime, set up the same way, which gives the same results
+    let mut rt = Runtime::default();
+    let (logger, counter) = setup(&mut rt)?;
+
+    let mut replayer = Replayer::new();
+    replayer.register::<u32>();
+    replayer.replay(&mut rt, &recording)?;
+
+    teardown(&mut rt, logger, counter)?;
+
+    Ok(())
+}
+
+fn setup(rt: &mut Runtime) -> Result<(Addr<Logger>, Addr<Counter>), Error> {
+    let logger = rt.insert("logger", Logger)?;
+    let counter = Counter {
+        total: 0,
+        report: logger.sender(),
+    };
+    let counter = rt.insert("counter", counter)?;
+
+    Ok((logger, counter))
+}
+
+fn teardown(rt: &mut Runtime, logger: Addr<Logger>, counter: Addr<Counter>) -> Result<(), Error> {
+    rt.remove(counter.id())??;
+    rt.remove(logger.id())??;
+    Ok(())
+}
+
+struct Counter {
+    total: u32,
+    report: Sender<u32>,
+}
+
+impl Actor for Counter {
+    type Message = u32;
+
+    fn handle(&mut self, ctx: &mut Context, amount: u32) -> Result<ControlFlow<()>, ActorError> {
+        self.total += amount;
+
+        // Messages between actors are recorded too, but they're not replayed, the actor sends
+        // them again itself when replaying
+        self.report
+            .send(ctx, self.total)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+struct Logger;
+
+impl Actor for Logger {
+    type Message = u32;
+
+    fn handle(&mut self, _ctx: &mut Context, total: u32) -> Result<ControlFlow<()>, ActorError> {
+        event!(Level::INFO, total, "total");
+        Ok(ControlFlow::Continue(()))
+    }
+}
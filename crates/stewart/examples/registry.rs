@@ -0,0 +1,101 @@
+use anyhow::{Context as _, Error};
+use std::ops::ControlFlow;
+use stewart::{Actor, ActorError, Context, Key, Runtime};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+
+    // Services register themselves when started, nobody needs to pass their senders around
+    let storage = rt.insert("storage", Storage)?;
+    let app = rt.insert("app", App)?;
+
+    // Anyone can look them up by key
+    let sender = rt.lookup::<String>(&Key::new("storage"))?;
+    sender.send(&mut rt, "from main".to_string())??;
+    rt.process()?;
+
+    // Scoped keys let an actor find its own children, without conflicting with global keys
+    let cache = rt.lookup::<String>(&Key::scoped(app.id(), "storage"))?;
+    cache.send(&mut rt, "from main".to_string())??;
+    rt.process()?;
+
+    // Registrations are removed with their actor, or with their scope
+    rt.remove(storage.id())??;
+    rt.remove(app.id())??;
+    let result = rt.lookup::<String>(&Key::new("storage"));
+    event!(Level::INFO, error = ?result.err(), "after removal");
+
+    Ok(())
+}
+
+struct Storage;
+
+impl Actor for Storage {
+    type Message = String;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        let sender = ctx.self_sender::<String>();
+        ctx.register("storage", sender)
+            .context("failed to register")?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: String,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        event!(Level::INFO, message, "storing");
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+struct App;
+
+impl Actor for App {
+    type Message = ();
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        // A cache in front of storage, only visible under the app
+        let cache = ctx
+            .insert_child("cache", Cache)
+            .context("failed to insert cache")?;
+        let key = Key::scoped(ctx.self_id(), "storage");
+        ctx.register(key, cache.sender())
+            .context("failed to register")?;
+
+        Ok(())
+    }
+
+    fn handle(&mut self, _ctx: &mut Context, _message: ()) -> Result<ControlFlow<()>, ActorError> {
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+struct Cache;
+
+impl Actor for Cache {
+    type Message = String;
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: String,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        event!(Level::INFO, message, "caching");
+
+        // Pass it on to the global storage service
+        let storage = ctx
+            .lookup::<String>(&Key::new("storage"))
+            .context("failed to find storage")?;
+        storage
+            .send(ctx, message)
+            .context("failed to send")?
+            .context("failed to send")?;
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Error;
+use std::ops::ControlFlow;
+use stewart::{Actor, ActorError, Context, DecodeError, Runtime, RuntimeSnapshot, Snapshot};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+    let player = rt.insert("player", Player { position: 0 })?;
+    rt.enable_snapshot(player)?;
+    let pickup = rt.insert("pickup", Player { position: 5 })?;
+    rt.enable_snapshot(pickup)?;
+
+    // Frame 1, our own input
+    player.send(&mut rt, 2)??;
+    rt.process()?;
+
+    // Save the state before frame 2, including input that's already queued
+    player.send(&mut rt, 3)??;
+    let snapshot = rt.snapshot()?;
+
+    // Snapshots can be stored as bytes, for example to send them to a peer that's out of sync
+    let bytes = snapshot.to_bytes();
+    event!(Level::INFO, bytes = bytes.len(), "saved snapshot");
+
+    // Frame 2 and 3, predicting the remote player didn't move, picking up the pickup and
+    // spawning a projectile
+    rt.process()?;
+    rt.remove(pickup.id())??;
+    let projectile = rt.insert("projectile", Player { position: 5 })?;
+    rt.enable_snapshot(projectile)?;
+    player.send(&mut rt, 1)??;
+    rt.process()?;
+
+    // Remote input for frame 2 arrives late, roll back and re-simulate
+    // Restoring also despawns the projectile, and brings back the pickup with the same id
+    rt.restore(&RuntimeSnapshot::from_bytes(&bytes)?)?;
+    event!(
+        Level::INFO,
+        pickup = rt.actor_info(pickup.id()).is_some(),
+        projectile = rt.actor_info(projectile.id()).is_some(),
+        "restored"
+    );
+
+    player.send(&mut rt, -10)??;
+    rt.process()?;
+    player.send(&mut rt, 1)??;
+    rt.process()?;
+
+    rt.remove(pickup.id())??;
+    rt.remove(player.id())??;
+
+    Ok(())
+}
+
+struct Player {
+    position: i32,
+}
+
+impl Snapshot for Player {
+    fn save(&self, buffer: &mut Vec<u8>) {
+        self.position.save(buffer);
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let position = i32::load(bytes)?;
+        Ok(Self { position })
+    }
+}
+
+impl Actor for Player {
+    type Message = i32;
+
+    fn handle(&mut self, _ctx: &mut Context, delta: i32) -> Result<ControlFlow<()>, ActorError> {
+        self.position += delta;
+        event!(Level::INFO, delta, position = self.position, "moved");
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -0,0 +1,87 @@
+use anyhow::{bail, Error};
+use std::ops::ControlFlow;
+use stewart::{Actor, ActorError, Context, Runtime, StopReason, Strategy, SupervisorOptions};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+
+    // Create a supervisor, which will restart its children when they fail
+    let options = SupervisorOptions {
+        strategy: Strategy::OneForOne,
+        ..Default::default()
+    };
+    let supervisor = rt.insert_supervisor(None, "supervisor", options)?;
+
+    // Insert an actor under the supervisor, using a factory so it can be re-created
+    // The returned address is typed, so only `Message` can be sent to it
+    let addr = rt.insert_supervised(supervisor, "counter", |_rt, _id| Ok(Counter { count: 0 }))?;
+
+    // Count a bit, then make the actor fail
+    addr.send(&mut rt, Message::Increment)??;
+    addr.send(&mut rt, Message::Increment)??;
+    addr.send(&mut rt, Message::Fail)??;
+    rt.process()?;
+
+    // The actor has been restarted with fresh state, but the same address
+    addr.send(&mut rt, Message::Increment)??;
+    rt.process()?;
+
+    // Panics are caught, and handled the same as errors
+    addr.send(&mut rt, Message::Panic)??;
+    rt.process()?;
+
+    addr.send(&mut rt, Message::Increment)??;
+    rt.process()?;
+
+    // Removing the supervisor also removes its children
+    rt.remove(supervisor)??;
+
+    Ok(())
+}
+
+enum Message {
+    Increment,
+    Fail,
+    Panic,
+}
+
+struct Counter {
+    count: usize,
+}
+
+impl Actor for Counter {
+    type Message = Message;
+
+    fn started(&mut self, _ctx: &mut Context) -> Result<(), ActorError> {
+        event!(Level::INFO, "counter started");
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Message::Increment => {
+                self.count += 1;
+                event!(Level::INFO, count = self.count, "incremented");
+            }
+            Message::Fail => fail()?,
+            Message::Panic => panic!("counter asked to panic"),
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn stopping(&mut self, _ctx: &mut Context, reason: StopReason) {
+        event!(Level::INFO, ?reason, count = self.count, "counter stopping");
+    }
+}
+
+fn fail() -> Result<(), Error> {
+    bail!("counter asked to fail")
+}
@@ -0,0 +1,78 @@
+use anyhow::Error;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+use stewart::{Actor, ActorError, Context, Runtime, TimerHandle};
+use tracing::{event, Level};
+
+fn main() -> Result<(), Error> {
+    devutils::init_logging();
+
+    let mut rt = Runtime::default();
+
+    let ticker = Ticker {
+        interval: None,
+        ticks: 0,
+    };
+    rt.insert("ticker", ticker)?;
+
+    // Drive the runtime, sleeping until the next timer is due
+    // Event loops, like the one in stewart-mio, do the same while waiting for IO
+    rt.process()?;
+    while let Some(deadline) = rt.next_deadline() {
+        std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+        rt.process()?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone)]
+enum Message {
+    Tick,
+    Stop,
+}
+
+struct Ticker {
+    interval: Option<TimerHandle>,
+    ticks: usize,
+}
+
+impl Actor for Ticker {
+    type Message = Message;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        let sender = ctx.self_sender();
+
+        // Tick regularly, and stop a while later
+        let handle = ctx.send_interval(sender.clone(), Duration::from_millis(100), Message::Tick);
+        self.interval = Some(handle);
+        ctx.send_after(sender, Duration::from_millis(550), Message::Stop);
+
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {
+            Message::Tick => {
+                self.ticks += 1;
+                event!(Level::INFO, ticks = self.ticks, "tick");
+            }
+            Message::Stop => {
+                event!(Level::INFO, "stopping");
+
+                // Timers keep going until cancelled, or until sending fails
+                if let Some(handle) = self.interval.take() {
+                    ctx.cancel_timer(handle);
+                }
+
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Error;
 use std::ops::ControlFlow;
 use thiserror::Error;
 
-use crate::Runtime;
+use crate::{Context, StopReason};
 
 /// Actor identity and processing implementation trait.
 pub trait Actor: 'static {
@@ -12,12 +12,33 @@ pub trait Actor: 'static {
     /// Most actors will have a different internal message type that's not exposed.
     type Message;
 
+    /// Called after the actor has been started, before it processes any messages.
+    ///
+    /// Returning an error is handled the same as returning an error from `handle`.
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        let _ = ctx;
+        Ok(())
+    }
+
     /// Process a message.
+    ///
+    /// A panic while processing is caught, and handled the same as returning an error.
     fn handle(
         &mut self,
-        rt: &mut Runtime,
+        ctx: &mut Context,
         message: Self::Message,
     ) -> Result<ControlFlow<()>, ActorError>;
+
+    /// Called when the actor is about to stop, before its children are removed.
+    ///
+    /// Unlike `Drop`, this has access to the context, so the actor can still send messages, for
+    /// example to tell others that it's closing.
+    fn stopping(&mut self, ctx: &mut Context, reason: StopReason) {
+        let _ = (ctx, reason);
+    }
+
+    /// Called after the actor and its children have been removed from the runtime.
+    fn stopped(&mut self) {}
 }
 
 /// Internal error in actor.
@@ -0,0 +1,76 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::sender::Sender;
+use crate::{Actor, Id, InternalError, Runtime, SendError};
+
+/// Typed address of an actor inserted into a runtime.
+///
+/// Unlike `Id`, an `Addr` knows the type of the actor, so sending a message of the wrong type
+/// fails to compile, rather than failing with `SendError::WrongType`.
+/// Use `id` to get the untyped `Id`, for example for monitoring.
+pub struct Addr<A> {
+    id: Id,
+    _a: PhantomData<fn() -> A>,
+}
+
+impl<A> Addr<A>
+where
+    A: Actor,
+{
+    pub(crate) fn new(id: Id) -> Self {
+        Self {
+            id,
+            _a: PhantomData,
+        }
+    }
+
+    /// Get the untyped `Id` of the actor.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Create a sender to the actor.
+    pub fn sender(&self) -> Sender<A::Message> {
+        Sender::new(self.id)
+    }
+
+    /// Send a message to the actor.
+    ///
+    /// Unlike sending by `Id`, this can't fail with `SendError::WrongType`.
+    pub fn send(
+        &self,
+        rt: &mut Runtime,
+        message: A::Message,
+    ) -> Result<Result<(), SendError>, InternalError> {
+        rt.send(self.id, message)
+    }
+}
+
+impl<A> Clone for Addr<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A> Copy for Addr<A> {}
+
+impl<A> PartialEq for Addr<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A> Eq for Addr<A> {}
+
+impl<A> Debug for Addr<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Addr").field(&self.id).finish()
+    }
+}
+
+impl<A> From<Addr<A>> for Id {
+    fn from(addr: Addr<A>) -> Self {
+        addr.id
+    }
+}
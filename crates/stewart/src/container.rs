@@ -1,21 +1,89 @@
-use anyhow::Context;
 use std::any::Any;
 use std::collections::VecDeque;
 use std::ops::ControlFlow;
 
-use crate::{Actor, ActorError, InternalError, Runtime};
+use crate::runtime::Budget;
+use crate::{Actor, ActorError, Context, Id, Runtime, StopReason};
+
+/// Type-erased message queue of an actor.
+///
+/// The queue is stored separately from the actor, so messages can be sent to an actor while it's
+/// being processed.
+pub trait AnyQueue {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Name of the message type in the queue.
+    fn type_name(&self) -> &'static str;
+
+    /// Take all messages, boxed as `Any`.
+    fn drain_any(&mut self) -> Vec<Box<dyn Any>>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool;
+
+    fn clear(&mut self);
+}
+
+impl<M> AnyQueue for VecDeque<M>
+where
+    M: 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<M>()
+    }
+
+    fn drain_any(&mut self) -> Vec<Box<dyn Any>> {
+        self.drain(..)
+            .map(|m| Box::new(m) as Box<dyn Any>)
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        VecDeque::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self);
+    }
+}
 
 pub trait AnyActorContainer {
-    fn push_message(&mut self, slot: &mut dyn Any) -> Result<(), InternalError>;
+    fn as_any(&self) -> &dyn Any;
+
+    fn started(&mut self, rt: &mut Runtime, id: Id) -> Result<(), ActorError>;
 
-    fn process(&mut self, rt: &mut Runtime) -> Result<ControlFlow<()>, ActorError>;
+    /// Process pending messages, until none are left or `budget` runs out.
+    fn process(
+        &mut self,
+        rt: &mut Runtime,
+        id: Id,
+        budget: &mut Budget,
+    ) -> Result<ControlFlow<()>, ActorError>;
+
+    fn stopping(&mut self, rt: &mut Runtime, id: Id, reason: StopReason);
+
+    fn stopped(&mut self);
 }
 
 pub struct ActorContainer<A>
 where
     A: Actor,
 {
-    queue: VecDeque<A::Message>,
     actor: A,
 }
 
@@ -24,10 +92,15 @@ where
     A: Actor,
 {
     pub fn new(actor: A) -> Self {
-        Self {
-            queue: VecDeque::new(),
-            actor,
-        }
+        Self { actor }
+    }
+
+    pub fn create_queue() -> Box<dyn AnyQueue> {
+        Box::new(VecDeque::<A::Message>::new())
+    }
+
+    pub fn actor(&self) -> &A {
+        &self.actor
     }
 }
 
@@ -35,22 +108,30 @@ impl<A> AnyActorContainer for ActorContainer<A>
 where
     A: Actor,
 {
-    fn push_message(&mut self, slot: &mut dyn Any) -> Result<(), InternalError> {
-        // Try downcasting the slot
-        let Some(slot) = slot.downcast_mut::<Option<A::Message>>() else {
-            return Ok(());
-        };
-
-        // Take the message out, and store it
-        let message = slot.take().context("no message in slot")?;
-        self.queue.push_back(message);
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 
-        Ok(())
+    fn started(&mut self, rt: &mut Runtime, id: Id) -> Result<(), ActorError> {
+        let mut ctx = Context::new(rt, id);
+        self.actor.started(&mut ctx)
     }
 
-    fn process(&mut self, rt: &mut Runtime) -> Result<ControlFlow<()>, ActorError> {
-        while let Some(message) = self.queue.pop_front() {
-            let flow = self.actor.handle(rt, message)?;
+    fn process(
+        &mut self,
+        rt: &mut Runtime,
+        id: Id,
+        budget: &mut Budget,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        let mut ctx = Context::new(rt, id);
+
+        while budget.available() {
+            let Some(message) = ctx.pop_message::<A::Message>(id) else {
+                break;
+            };
+            budget.consume();
+
+            let flow = self.actor.handle(&mut ctx, message)?;
 
             if flow.is_break() {
                 return Ok(ControlFlow::Break(()));
@@ -59,4 +140,13 @@ where
 
         Ok(ControlFlow::Continue(()))
     }
+
+    fn stopping(&mut self, rt: &mut Runtime, id: Id, reason: StopReason) {
+        let mut ctx = Context::new(rt, id);
+        self.actor.stopping(&mut ctx, reason);
+    }
+
+    fn stopped(&mut self) {
+        self.actor.stopped();
+    }
 }
@@ -0,0 +1,99 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::sender::{Mailbox, Sender};
+use crate::{Actor, Addr, Id, InternalError, Key, RegisterError, Runtime};
+
+/// Context an actor is called in, giving access to the runtime and the actor's own identity.
+///
+/// `Context` dereferences to `Runtime`, so it can be passed anywhere a `&mut Runtime` is expected.
+pub struct Context<'a> {
+    rt: &'a mut Runtime,
+    id: Id,
+}
+
+impl<'a> Context<'a> {
+    pub(crate) fn new(rt: &'a mut Runtime, id: Id) -> Self {
+        Self { rt, id }
+    }
+
+    /// Get the `Id` of the current actor.
+    pub fn self_id(&self) -> Id {
+        self.id
+    }
+
+    /// Create a sender to the current actor.
+    ///
+    /// `M` should be the actor's message type, sending will fail with `SendError::WrongType`
+    /// otherwise.
+    pub fn self_sender<M>(&self) -> Sender<M>
+    where
+        M: 'static,
+    {
+        Sender::new(self.id)
+    }
+
+    /// Create a mailbox owned by the current actor.
+    ///
+    /// See `Runtime::mailbox` for more information.
+    pub fn mailbox<T>(&self) -> Result<Mailbox<T>, InternalError>
+    where
+        T: 'static,
+    {
+        self.rt.mailbox(self.id)
+    }
+
+    /// Get the runtime the current actor is in.
+    pub fn runtime(&mut self) -> &mut Runtime {
+        self.rt
+    }
+
+    /// Insert an actor into the runtime, as a child of the current actor.
+    ///
+    /// See `Runtime::insert_child` for more information.
+    pub fn insert_child<A>(
+        &mut self,
+        name: &'static str,
+        actor: A,
+    ) -> Result<Addr<A>, InternalError>
+    where
+        A: Actor,
+    {
+        self.rt.insert_child(self.id, name, actor)
+    }
+
+    /// Create a new actor entry as a child of the current actor, without an actor implementation
+    /// yet.
+    ///
+    /// See `Runtime::create_child` for more information.
+    pub fn create_child(&mut self, name: &'static str) -> Result<Id, InternalError> {
+        self.rt.create_child(self.id, name)
+    }
+
+    /// Register a sender under `key`, owned by the current actor.
+    ///
+    /// See `Runtime::register` for more information.
+    pub fn register<M>(
+        &mut self,
+        key: impl Into<Key>,
+        sender: Sender<M>,
+    ) -> Result<(), RegisterError>
+    where
+        M: 'static,
+    {
+        self.rt.register(self.id, key, sender)
+    }
+}
+
+impl Deref for Context<'_> {
+    type Target = Runtime;
+
+    fn deref(&self) -> &Runtime {
+        self.rt
+    }
+}
+
+impl DerefMut for Context<'_> {
+    fn deref_mut(&mut self) -> &mut Runtime {
+        self.rt
+    }
+}
@@ -6,7 +6,10 @@
 //! stewart book.
 
 mod actor;
+mod addr;
 mod container;
+mod context;
+pub mod persist;
 mod runtime;
 pub mod sender;
 
@@ -15,7 +18,17 @@ use thiserror::Error;
 
 pub use self::{
     actor::{Actor, ActorError},
-    runtime::{Id, ProcessError, RemoveError, Runtime, SendError},
+    addr::Addr,
+    context::Context,
+    runtime::{
+        ActorInfo, ActorOptions, ActorStats, CapacityAvailable, Clock, DeadLetter,
+        DeadLetterReason, DecodeError, ErrorPolicy, Id, Intensity, Key, LookupError, MessageOrigin,
+        MonitorError, Overflow, ProcessCall, ProcessError, ProcessErrorKind, Recordable,
+        RecordedEvent, RecordedMessage, Recording, RegisterError, RemoveError, ReplayError,
+        Replayer, Runtime, RuntimeBuilder, RuntimeObserver, RuntimeSnapshot, SendError, Snapshot,
+        SnapshotError, StopReason, Strategy, SupervisorOptions, SystemClock, Terminated,
+        TimerHandle,
+    },
 };
 
 /// Internal error in stewart.
@@ -0,0 +1,146 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use tracing::{event, Level};
+
+/// Storage backend for the events and snapshots of a persistent actor.
+///
+/// Every event and snapshot has a sequence number, increasing by one for every event. A snapshot
+/// with sequence number `n` includes all events up to and including `n`.
+pub trait Journal {
+    /// Durably append an event, before it's applied.
+    fn append(&mut self, seq: u64, event: &[u8]) -> io::Result<()>;
+
+    /// Durably store a snapshot, after which earlier events may be discarded.
+    fn save_snapshot(&mut self, seq: u64, state: &[u8]) -> io::Result<()>;
+
+    /// Load the latest snapshot, and all events stored after it.
+    fn load(&mut self) -> io::Result<JournalContents>;
+}
+
+/// Contents loaded from a journal.
+#[derive(Debug, Default, Clone)]
+pub struct JournalContents {
+    /// The latest snapshot and its sequence number, if any.
+    pub snapshot: Option<(u64, Vec<u8>)>,
+    /// Events stored after the snapshot, with their sequence numbers, in order.
+    pub events: Vec<(u64, Vec<u8>)>,
+}
+
+/// Journal storing events and snapshots as files in a directory.
+///
+/// Events are appended to `journal.bin`, and synced to disk before they're applied.
+/// Snapshots are written to `snapshot.bin` atomically, after which the journal is truncated.
+/// An event only partially written when the process stopped is discarded when loading.
+pub struct FileJournal {
+    directory: PathBuf,
+    file: File,
+}
+
+impl FileJournal {
+    /// Open the journal in `directory`, creating it if it doesn't exist yet.
+    pub fn open(directory: impl Into<PathBuf>) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(directory.join("journal.bin"))?;
+
+        Ok(Self { directory, file })
+    }
+}
+
+impl Journal for FileJournal {
+    fn append(&mut self, seq: u64, event: &[u8]) -> io::Result<()> {
+        let mut record = Vec::with_capacity(16 + event.len());
+        record.extend_from_slice(&seq.to_le_bytes());
+        record.extend_from_slice(&(event.len() as u64).to_le_bytes());
+        record.extend_from_slice(event);
+
+        self.file.write_all(&record)?;
+        self.file.sync_data()
+    }
+
+    fn save_snapshot(&mut self, seq: u64, state: &[u8]) -> io::Result<()> {
+        // Write to a temporary file first, so a crash can't leave a half-written snapshot
+        let temporary = self.directory.join("snapshot.tmp");
+        let mut file = File::create(&temporary)?;
+        file.write_all(&seq.to_le_bytes())?;
+        file.write_all(state)?;
+        file.sync_all()?;
+        fs::rename(&temporary, self.directory.join("snapshot.bin"))?;
+
+        // The rename itself has to be durable before the events it replaces are dropped
+        sync_directory(&self.directory)?;
+
+        // Events before the snapshot aren't needed anymore, if this fails they're skipped by seq
+        self.file.set_len(0)?;
+        self.file.sync_all()
+    }
+
+    fn load(&mut self) -> io::Result<JournalContents> {
+        let snapshot = match fs::read(self.directory.join("snapshot.bin")) {
+            Ok(bytes) => {
+                let (seq, state) = split_u64(&bytes)?;
+                Some((seq, state.to_vec()))
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        };
+        let after = snapshot.as_ref().map(|(seq, _)| *seq).unwrap_or(0);
+
+        let mut bytes = Vec::new();
+        let mut file = File::open(self.directory.join("journal.bin"))?;
+        file.read_to_end(&mut bytes)?;
+
+        let mut events = Vec::new();
+        let mut rest = bytes.as_slice();
+        while rest.len() >= 16 {
+            let (seq, tail) = split_u64(rest)?;
+            let (len, tail) = split_u64(tail)?;
+            let Some(event) = usize::try_from(len).ok().and_then(|len| tail.get(..len)) else {
+                break;
+            };
+
+            // Events already included in the snapshot are skipped
+            if seq > after {
+                events.push((seq, event.to_vec()));
+            }
+            rest = &tail[event.len()..];
+        }
+
+        // Drop a partially written event at the end, so new events are appended after valid ones
+        if !rest.is_empty() {
+            event!(Level::WARN, "discarding partially written event");
+            let valid = bytes.len() - rest.len();
+            self.file.set_len(valid as u64)?;
+        }
+
+        Ok(JournalContents { snapshot, events })
+    }
+}
+
+fn split_u64(bytes: &[u8]) -> io::Result<(u64, &[u8])> {
+    if bytes.len() < 8 {
+        return Err(io::Error::new(ErrorKind::InvalidData, "record too short"));
+    }
+
+    let (value, rest) = bytes.split_at(8);
+    let value = u64::from_le_bytes(value.try_into().expect("length checked"));
+    Ok((value, rest))
+}
+
+#[cfg(unix)]
+fn sync_directory(directory: &Path) -> io::Result<()> {
+    File::open(directory)?.sync_all()
+}
+
+/// Directories can't be opened as files on other platforms, so renames are best effort there.
+#[cfg(not(unix))]
+fn sync_directory(_directory: &Path) -> io::Result<()> {
+    Ok(())
+}
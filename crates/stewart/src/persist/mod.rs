@@ -0,0 +1,14 @@
+//! Event-sourced persistent actors.
+//!
+//! A persistent actor doesn't change its state directly. Instead, it persists events describing
+//! the change, which are written to a journal before they're applied. When the actor is inserted
+//! again, for example after the process restarted, its state is rebuilt by replaying the
+//! journal. Snapshots of the state are taken periodically, so replaying stays fast.
+
+mod journal;
+mod persistent;
+
+pub use self::{
+    journal::{FileJournal, Journal, JournalContents},
+    persistent::{PersistContext, PersistOptions, Persistent, PersistentActor},
+};
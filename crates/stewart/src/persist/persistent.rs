@@ -0,0 +1,248 @@
+use std::marker::PhantomData;
+use std::ops::{ControlFlow, Deref, DerefMut};
+
+use anyhow::Context as _;
+use tracing::{event, Level};
+
+use crate::persist::Journal;
+use crate::{Actor, ActorError, Context, Snapshot, StopReason};
+
+/// Event-sourced actor state, persisted through a journal.
+///
+/// Instead of changing its state while handling a message, the state persists events with
+/// `PersistContext::persist`. Every event is written to the journal before it's applied, so the
+/// state can be rebuilt by applying the journal's events again.
+///
+/// Insert it into the runtime wrapped in a `PersistentActor`.
+pub trait Persistent: Snapshot {
+    /// The message type this actor processes.
+    type Message;
+
+    /// The event type describing changes to the state.
+    type Event: Snapshot;
+
+    /// Called after the state has been recovered from the journal, before any messages are
+    /// processed.
+    fn recovered(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        let _ = ctx;
+        Ok(())
+    }
+
+    /// Process a message, persisting events for changes to the state.
+    fn handle(
+        &mut self,
+        ctx: &mut PersistContext<Self::Event>,
+        message: Self::Message,
+    ) -> Result<ControlFlow<()>, ActorError>;
+
+    /// Apply an event to the state.
+    ///
+    /// This is called both when persisting new events and when recovering, so it should only
+    /// change the state, and do so deterministically.
+    fn apply(&mut self, event: &Self::Event);
+
+    /// Called when the actor is about to stop.
+    ///
+    /// See `Actor::stopping` for more information.
+    fn stopping(&mut self, ctx: &mut Context, reason: StopReason) {
+        let _ = (ctx, reason);
+    }
+}
+
+/// Configuration of a persistent actor.
+#[derive(Debug, Clone, Copy)]
+pub struct PersistOptions {
+    /// Amount of events after which a snapshot is taken, `None` to never take snapshots.
+    pub snapshot_every: Option<usize>,
+}
+
+impl Default for PersistOptions {
+    fn default() -> Self {
+        Self {
+            snapshot_every: Some(1000),
+        }
+    }
+}
+
+/// Actor wrapping `Persistent` state, recovering it from a journal when started.
+///
+/// Because the state is recovered in `Actor::started`, this can be inserted like any other
+/// actor, including as a child or by a supervisor restarting it.
+pub struct PersistentActor<P>
+where
+    P: Persistent,
+{
+    state: P,
+    journal: JournalState,
+}
+
+struct JournalState {
+    journal: Box<dyn Journal>,
+    options: PersistOptions,
+    seq: u64,
+    since_snapshot: usize,
+}
+
+impl<P> PersistentActor<P>
+where
+    P: Persistent,
+{
+    /// Create a new persistent actor.
+    ///
+    /// `initial` is the state used when the journal doesn't contain a snapshot yet.
+    pub fn new(initial: P, journal: impl Journal + 'static, options: PersistOptions) -> Self {
+        Self {
+            state: initial,
+            journal: JournalState {
+                journal: Box::new(journal),
+                options,
+                seq: 0,
+                since_snapshot: 0,
+            },
+        }
+    }
+
+    fn recover(&mut self) -> Result<(), ActorError> {
+        let contents = self
+            .journal
+            .journal
+            .load()
+            .context("failed to load journal")?;
+
+        if let Some((seq, bytes)) = contents.snapshot {
+            self.state = P::load(&bytes).context("failed to decode snapshot")?;
+            self.journal.seq = seq;
+        }
+
+        let count = contents.events.len();
+        for (seq, bytes) in contents.events {
+            let event = P::Event::load(&bytes).context("failed to decode event")?;
+            self.state.apply(&event);
+            self.journal.seq = seq;
+        }
+
+        self.journal.since_snapshot = count;
+        event!(
+            Level::DEBUG,
+            seq = self.journal.seq,
+            count,
+            "recovered state"
+        );
+
+        Ok(())
+    }
+
+    fn snapshot_if_needed(&mut self) -> Result<(), ActorError> {
+        let Some(every) = self.journal.options.snapshot_every else {
+            return Ok(());
+        };
+
+        if self.journal.since_snapshot < every {
+            return Ok(());
+        }
+
+        let mut buffer = Vec::new();
+        self.state.save(&mut buffer);
+        self.journal
+            .journal
+            .save_snapshot(self.journal.seq, &buffer)
+            .context("failed to save snapshot")?;
+        self.journal.since_snapshot = 0;
+
+        event!(Level::DEBUG, seq = self.journal.seq, "saved snapshot");
+
+        Ok(())
+    }
+}
+
+impl<P> Actor for PersistentActor<P>
+where
+    P: Persistent,
+{
+    type Message = P::Message;
+
+    fn started(&mut self, ctx: &mut Context) -> Result<(), ActorError> {
+        self.recover()?;
+        self.snapshot_if_needed()?;
+        self.state.recovered(ctx)
+    }
+
+    fn handle(
+        &mut self,
+        ctx: &mut Context,
+        message: P::Message,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        let mut pctx = PersistContext {
+            ctx,
+            journal: &mut self.journal,
+            _event: PhantomData,
+        };
+        let result = self.state.handle(&mut pctx, message);
+
+        // Events persisted before an error have still been applied, so still count them
+        self.snapshot_if_needed()?;
+
+        result
+    }
+
+    fn stopping(&mut self, ctx: &mut Context, reason: StopReason) {
+        self.state.stopping(ctx, reason);
+    }
+}
+
+/// Context of a persistent actor handling a message.
+///
+/// Dereferences to the regular actor `Context`.
+pub struct PersistContext<'a, 'b, E> {
+    ctx: &'a mut Context<'b>,
+    journal: &'a mut JournalState,
+    _event: PhantomData<E>,
+}
+
+impl<E> PersistContext<'_, '_, E>
+where
+    E: Snapshot,
+{
+    /// Write an event to the journal, and then apply it to `state`.
+    ///
+    /// If writing fails, the event isn't applied, and the error should be returned from the
+    /// handler.
+    pub fn persist<P>(&mut self, state: &mut P, event: E) -> Result<(), ActorError>
+    where
+        P: Persistent<Event = E>,
+    {
+        let seq = self.journal.seq + 1;
+
+        let mut buffer = Vec::new();
+        event.save(&mut buffer);
+        self.journal
+            .journal
+            .append(seq, &buffer)
+            .context("failed to write event")?;
+
+        self.journal.seq = seq;
+        self.journal.since_snapshot += 1;
+        state.apply(&event);
+
+        Ok(())
+    }
+
+    /// Sequence number of the last persisted event.
+    pub fn seq(&self) -> u64 {
+        self.journal.seq
+    }
+}
+
+impl<'b, E> Deref for PersistContext<'_, 'b, E> {
+    type Target = Context<'b>;
+
+    fn deref(&self) -> &Context<'b> {
+        self.ctx
+    }
+}
+
+impl<E> DerefMut for PersistContext<'_, '_, E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.ctx
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::Instant;
+
+/// Limit on how much work may be done while processing.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Budget {
+    messages: usize,
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    pub fn new(messages: usize, deadline: Option<Instant>) -> Self {
+        Self { messages, deadline }
+    }
+
+    /// Check if there's budget left to process another message.
+    pub fn available(&self) -> bool {
+        if self.messages == 0 {
+            return false;
+        }
+
+        match self.deadline {
+            Some(deadline) => Instant::now() < deadline,
+            None => true,
+        }
+    }
+
+    /// Get the amount of messages left in the budget.
+    pub fn remaining(&self) -> usize {
+        self.messages
+    }
+
+    /// Use up the budget for one message.
+    pub fn consume(&mut self) {
+        self.messages -= 1;
+    }
+
+    /// Split off a budget for one actor activation, limited to at most `messages` messages.
+    pub fn split(&self, messages: usize) -> Self {
+        Self {
+            messages: self.messages.min(messages),
+            deadline: self.deadline,
+        }
+    }
+
+    /// Use up what was used of an activation budget, split off with the same `messages` limit.
+    pub fn join(&mut self, messages: usize, activation: Budget) {
+        let start = self.messages.min(messages);
+        self.messages -= start - activation.messages;
+    }
+}
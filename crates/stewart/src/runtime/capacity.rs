@@ -0,0 +1,66 @@
+use thunderdome::Index;
+use tracing::{event, instrument, Level};
+
+use crate::runtime::{ActorEntry, Id, Runtime};
+use crate::sender::Sender;
+use crate::{InternalError, SendError};
+
+/// Notice that an actor's mailbox has room for messages again.
+#[derive(Debug, Clone, Copy)]
+pub struct CapacityAvailable {
+    /// The actor that has room again.
+    pub id: Id,
+}
+
+impl Runtime {
+    /// Notify `watcher` once `target` has room for at least one more message.
+    ///
+    /// If `target` has room already, `watcher` is notified immediately.
+    /// This is typically used after a send failed with `SendError::Full`, to know when to retry.
+    #[instrument("Runtime::notify_capacity", level = "debug", skip_all)]
+    pub fn notify_capacity(
+        &mut self,
+        watcher: Sender<CapacityAvailable>,
+        target: Id,
+    ) -> Result<Result<(), SendError>, InternalError> {
+        let Some(entry) = self.actors.get_mut(target.index) else {
+            return Ok(Err(SendError::NotFound));
+        };
+
+        if is_full(entry) {
+            entry.capacity_watchers.push(watcher);
+        } else {
+            let notice = CapacityAvailable { id: target };
+            let _ = self.as_runtime(|rt| watcher.send(rt, notice))?;
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Notify watchers if the actor has room again.
+    pub(super) fn wake_capacity_watchers(&mut self, index: Index) {
+        let Some(entry) = self.actors.get_mut(index) else {
+            return;
+        };
+
+        if entry.capacity_watchers.is_empty() || is_full(entry) {
+            return;
+        }
+
+        let notice = CapacityAvailable { id: Id { index } };
+        for watcher in std::mem::take(&mut entry.capacity_watchers) {
+            if let Err(error) = self.as_runtime(|rt| watcher.send(rt, notice)) {
+                event!(Level::ERROR, ?error, "failed to send capacity notice");
+            }
+        }
+    }
+}
+
+fn is_full(entry: &ActorEntry) -> bool {
+    let Some(capacity) = entry.options.capacity else {
+        return false;
+    };
+    let len = entry.queue.as_ref().map(|q| q.len()).unwrap_or(0);
+
+    len >= capacity
+}
@@ -0,0 +1,139 @@
+use std::any::Any;
+use std::fmt::{self, Debug, Formatter};
+
+use thunderdome::Index;
+use tracing::{event, Level};
+
+use crate::runtime::{Id, Runtime};
+use crate::sender::Sender;
+use crate::InternalError;
+
+/// Message that couldn't be delivered to an actor.
+pub struct DeadLetter {
+    /// The actor the message was sent to.
+    pub target: Id,
+    /// Name of the message's type, for diagnostics.
+    pub type_name: &'static str,
+    /// Why the message couldn't be delivered.
+    pub reason: DeadLetterReason,
+    /// The message itself, downcast it to inspect or recover it.
+    pub message: Box<dyn Any>,
+}
+
+impl Debug for DeadLetter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("DeadLetter")
+            .field("target", &self.target)
+            .field("type_name", &self.type_name)
+            .field("reason", &self.reason)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reason a message couldn't be delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    /// No actor found for the target id.
+    NotFound,
+    /// The message is the wrong type for the target actor.
+    WrongType,
+    /// The target has been created, but not started yet.
+    NotStarted,
+    /// The target's mailbox was full, and the send was rejected.
+    Full,
+    /// The target's mailbox was full, and the message was dropped by its overflow policy.
+    Dropped,
+    /// The message was still pending when the target stopped, or was restarted.
+    Stopped,
+}
+
+impl Runtime {
+    /// Set where messages that can't be delivered are sent, or `None` to drop them.
+    ///
+    /// Only messages sent to an `Id` are caught, senders that don't target an actor directly,
+    /// like mailbox senders, report failure only to the caller.
+    pub fn set_dead_letters(&mut self, sink: Option<Sender<DeadLetter>>) {
+        self.dead_letters = sink;
+    }
+
+    pub(super) fn dead_letter<M>(
+        &mut self,
+        target: Id,
+        message: M,
+        reason: DeadLetterReason,
+    ) -> Result<(), InternalError>
+    where
+        M: 'static,
+    {
+        let type_name = std::any::type_name::<M>();
+        event!(Level::DEBUG, type_name, ?reason, "undeliverable message");
+
+        if self.dead_letters.is_none() {
+            return Ok(());
+        }
+
+        let letter = DeadLetter {
+            target,
+            type_name,
+            reason,
+            message: Box::new(message),
+        };
+        self.send_dead_letter(letter)
+    }
+
+    /// Send all pending messages of an actor to the dead letter sink, or drop them if there is none.
+    pub(super) fn drop_pending(&mut self, index: Index) -> Result<(), InternalError> {
+        let Some(queue) = self.actors.get_mut(index).and_then(|e| e.queue.as_mut()) else {
+            return Ok(());
+        };
+
+        if queue.is_empty() {
+            return Ok(());
+        }
+
+        if self.dead_letters.is_none() {
+            event!(
+                Level::DEBUG,
+                count = queue.len(),
+                "dropping pending messages"
+            );
+            queue.clear();
+            return Ok(());
+        }
+
+        let type_name = queue.type_name();
+        let messages = queue.drain_any();
+
+        let target = Id { index };
+        for message in messages {
+            let letter = DeadLetter {
+                target,
+                type_name,
+                reason: DeadLetterReason::Stopped,
+                message,
+            };
+            self.send_dead_letter(letter)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_dead_letter(&mut self, letter: DeadLetter) -> Result<(), InternalError> {
+        let Some(sink) = self.dead_letters.take() else {
+            return Ok(());
+        };
+
+        // Without a sink set while sending, failing to deliver the dead letter can't recurse
+        let result = self.as_runtime(|rt| sink.send(rt, letter));
+
+        if self.dead_letters.is_none() {
+            self.dead_letters = Some(sink);
+        }
+
+        if let Err(error) = result? {
+            event!(Level::ERROR, ?error, "failed to send dead letter");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,937 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Error};
+use thiserror::Error;
+use thunderdome::{Arena, Index};
+use tracing::{event, instrument, span, Level};
+
+mod budget;
+mod capacity;
+mod clock;
+mod dead_letter;
+mod monitor;
+mod observer;
+mod options;
+mod policy;
+mod record;
+mod registry;
+mod replay;
+mod scheduling;
+mod snapshot;
+mod stats;
+mod supervisor;
+mod timer;
+mod unwind;
+
+use crate::container::{ActorContainer, AnyQueue};
+use crate::sender::{Mailbox, PendingAsk, Sender};
+use crate::{container::AnyActorContainer, Actor, ActorError, Addr, InternalError};
+
+use self::policy::Policies;
+use self::record::Recorder;
+use self::registry::Registry;
+use self::scheduling::Scheduling;
+use self::snapshot::{SnapshotFns, SnapshotTypes};
+use self::supervisor::{Supervised, SupervisorState};
+use self::timer::Timers;
+use self::unwind::{catch_unwind, catch_unwind_hook};
+
+pub(crate) use self::{budget::Budget, timer::AnyTimer};
+
+pub use self::{
+    capacity::CapacityAvailable,
+    clock::{Clock, SystemClock},
+    dead_letter::{DeadLetter, DeadLetterReason},
+    monitor::{MonitorError, StopReason, Terminated},
+    observer::RuntimeObserver,
+    options::{ActorOptions, Overflow},
+    policy::{ErrorPolicy, RuntimeBuilder},
+    record::{
+        DecodeError, MessageOrigin, ProcessCall, Recordable, RecordedEvent, RecordedMessage,
+        Recording,
+    },
+    registry::{Key, LookupError, RegisterError},
+    replay::{ReplayError, Replayer},
+    snapshot::{RuntimeSnapshot, Snapshot, SnapshotError},
+    stats::{ActorInfo, ActorStats},
+    supervisor::{Intensity, Strategy, SupervisorOptions},
+    timer::TimerHandle,
+};
+
+/// Thread-local actor tracking and execution system.
+#[derive(Default)]
+pub struct Runtime {
+    actors: Arena<ActorEntry>,
+    /// Actors scheduled for processing.
+    ///
+    /// May contain indices of removed actors, these are skipped when processing.
+    queue: VecDeque<Index>,
+    /// Asks that haven't been replied to yet.
+    asks: Vec<Box<dyn PendingAsk>>,
+    timers: Timers,
+    activation_budget: Option<usize>,
+    process_budget: Option<usize>,
+    dead_letters: Option<Sender<DeadLetter>>,
+    policies: Policies,
+    registry: Registry,
+    observer: Option<Box<dyn RuntimeObserver>>,
+    /// Clock used for timers, `None` to use the system clock.
+    clock: Option<Box<dyn Clock>>,
+    scheduling: Scheduling,
+    recorder: Recorder,
+    /// Actor types and names that have been snapshot-enabled, to recreate removed actors.
+    snapshot_types: SnapshotTypes,
+    /// The actor currently being called, if any.
+    active: Option<Index>,
+}
+
+struct ActorEntry {
+    name: &'static str,
+    /// Name of the actor's type, `None` until the actor is started.
+    type_name: Option<&'static str>,
+    /// Pending messages, `None` until the actor is started.
+    queue: Option<Box<dyn AnyQueue>>,
+    /// The actor itself, `None` until started or while borrowed for processing.
+    container: Option<Box<dyn AnyActorContainer>>,
+    /// Set while the actor is in the scheduling queue.
+    queued: bool,
+    options: ActorOptions,
+    /// Senders to notify when this actor has room for messages again.
+    capacity_watchers: Vec<Sender<CapacityAvailable>>,
+    /// Senders to notify when this actor stops.
+    monitors: Vec<Sender<Terminated>>,
+    parent: Option<Index>,
+    /// Children of this actor, in the order they were inserted.
+    children: Vec<Index>,
+    /// Set if this actor is supervised by its parent.
+    supervised: Option<Supervised>,
+    /// Set if this actor is a supervisor.
+    supervisor: Option<SupervisorState>,
+    /// Registry keys this actor owns or scopes.
+    registrations: Vec<Key>,
+    stats: ActorStats,
+    /// Set if this actor is included in snapshots.
+    snapshot: Option<SnapshotFns>,
+    /// Set if this actor was removed while borrowed, to finish removing it once it's returned,
+    /// or while one of its children was borrowed, to finish once that child has been removed.
+    removing: Option<StopReason>,
+    /// Set if this actor was restarted by its supervisor while borrowed, to restart it once it's
+    /// returned.
+    restarting: bool,
+}
+
+impl ActorEntry {
+    fn new(name: &'static str, parent: Option<Index>) -> Self {
+        Self {
+            name,
+            type_name: None,
+            queue: None,
+            container: None,
+            queued: false,
+            options: ActorOptions::default(),
+            capacity_watchers: Vec::new(),
+            monitors: Vec::new(),
+            parent,
+            children: Vec::new(),
+            supervised: None,
+            supervisor: None,
+            registrations: Vec::new(),
+            stats: ActorStats::default(),
+            snapshot: None,
+            removing: None,
+            restarting: false,
+        }
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        let mut names = Vec::new();
+
+        for (_, node) in &self.actors {
+            names.push(node.name);
+        }
+
+        if !names.is_empty() {
+            event!(
+                Level::WARN,
+                ?names,
+                "actors not cleaned up before runtime drop",
+            );
+        }
+    }
+}
+
+impl Runtime {
+    /// Insert an actor into the runtime.
+    ///
+    /// The given `name` will be used in logging.
+    #[instrument("Runtime::insert", level = "debug", skip_all)]
+    pub fn insert<A>(&mut self, name: &'static str, actor: A) -> Result<Addr<A>, InternalError>
+    where
+        A: Actor,
+    {
+        let id = self.create(name)?;
+        self.start(id, actor)
+    }
+
+    /// Insert an actor into the runtime, as a child of `parent`.
+    ///
+    /// Children are removed when their parent is removed.
+    #[instrument("Runtime::insert_child", level = "debug", skip_all)]
+    pub fn insert_child<A>(
+        &mut self,
+        parent: Id,
+        name: &'static str,
+        actor: A,
+    ) -> Result<Addr<A>, InternalError>
+    where
+        A: Actor,
+    {
+        let id = self.create_child(parent, name)?;
+        self.start(id, actor)
+    }
+
+    /// Create a new actor entry, without an actor implementation yet.
+    ///
+    /// This lets you get the `Id` of an actor before constructing it, for example to create
+    /// senders that the actor itself needs to hold.
+    /// Messages sent to the actor before it's started with `start` fail with
+    /// `SendError::NotStarted`.
+    ///
+    /// The given `name` will be used in logging.
+    #[instrument("Runtime::create", level = "debug", skip_all)]
+    pub fn create(&mut self, name: &'static str) -> Result<Id, InternalError> {
+        self.create_entry(None, name)
+    }
+
+    /// Create a new actor entry as a child of `parent`, without an actor implementation yet.
+    ///
+    /// See `create` and `insert_child` for more information.
+    #[instrument("Runtime::create_child", level = "debug", skip_all)]
+    pub fn create_child(&mut self, parent: Id, name: &'static str) -> Result<Id, InternalError> {
+        self.create_entry(Some(parent.index), name)
+    }
+
+    fn create_entry(
+        &mut self,
+        parent: Option<Index>,
+        name: &'static str,
+    ) -> Result<Id, InternalError> {
+        event!(Level::DEBUG, name, "creating actor");
+
+        let entry = ActorEntry::new(name, parent);
+        let index = self.actors.insert(entry);
+
+        // Track the child on the parent
+        if let Some(parent) = parent {
+            let Some(parent_entry) = self.actors.get_mut(parent) else {
+                self.actors.remove(index);
+                return Err(anyhow!("failed to find parent").into());
+            };
+
+            parent_entry.children.push(index);
+        }
+
+        let id = Id { index };
+        self.observe(|o| o.inserted(id, name));
+
+        Ok(id)
+    }
+
+    /// Start a created actor, giving it its implementation.
+    #[instrument("Runtime::start", level = "debug", skip_all)]
+    pub fn start<A>(&mut self, id: Id, actor: A) -> Result<Addr<A>, InternalError>
+    where
+        A: Actor,
+    {
+        let entry = self
+            .actors
+            .get_mut(id.index)
+            .context("failed to find actor")?;
+
+        if entry.queue.is_some() {
+            return Err(anyhow!("actor already started").into());
+        }
+
+        // Store the actor itself
+        entry.queue = Some(ActorContainer::<A>::create_queue());
+        entry.type_name = Some(std::any::type_name::<A>());
+        let container = Box::new(ActorContainer::new(actor));
+        self.start_container(id.index, container)?;
+
+        Ok(Addr::new(id))
+    }
+
+    fn start_container(
+        &mut self,
+        index: Index,
+        mut container: Box<dyn AnyActorContainer>,
+    ) -> Result<(), Error> {
+        let name = self.actors.get(index).map(|e| e.name);
+        event!(Level::DEBUG, name, "starting actor");
+
+        let previous = self.active.replace(index);
+        let result = catch_unwind(|| container.started(self, Id { index }));
+        self.active = previous;
+
+        // The actor may have been removed by the hook
+        if !self.unborrow(index, container) || self.finish_removal(index)? {
+            return Ok(());
+        }
+
+        // Or restarted, in which case this instance has already been replaced
+        if self.finish_restart(index)? {
+            if let Err(error) = result {
+                event!(
+                    Level::ERROR,
+                    name,
+                    "actor failed while restarted:\n{:?}",
+                    error
+                );
+            }
+
+            return Ok(());
+        }
+
+        // Errors in the hook are handled the same as in processing
+        if let Err(error) = result {
+            if let Err(error) = self.handle_actor_error(index, error) {
+                // Without an address the caller can't reach the actor, so don't leave it behind
+                self.remove_with_reason(Id { index }, StopReason::Error)??;
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove an actor from the runtime.
+    ///
+    /// Children of the actor are removed first, in reverse order of insertion.
+    /// An actor that's currently being called, for example because it's removing itself, is
+    /// removed as soon as it returns.
+    #[instrument("Runtime::remove", level = "debug", skip_all)]
+    pub fn remove(&mut self, id: Id) -> Result<Result<(), RemoveError>, InternalError> {
+        self.remove_with_reason(id, StopReason::Removed)
+    }
+
+    fn remove_with_reason(
+        &mut self,
+        id: Id,
+        reason: StopReason,
+    ) -> Result<Result<(), RemoveError>, InternalError> {
+        event!(Level::DEBUG, ?reason, "removing actor");
+
+        let Some(entry) = self.actors.get_mut(id.index) else {
+            return Ok(Err(RemoveError::NotFound));
+        };
+
+        // If the actor is borrowed, for example because it's removing itself or an ancestor while
+        // processing, finish removing it once it's returned, so its hooks are still called
+        if entry.container.is_none() && entry.queue.is_some() {
+            entry.removing.get_or_insert(reason);
+            return Ok(Ok(()));
+        }
+
+        // Let the actor know it's stopping, while everything is still available, unless it already
+        // did and was only waiting for a borrowed child
+        let waiting = entry.removing.is_some();
+        if let Some(mut container) = entry.container.take_if(|_| !waiting) {
+            let previous = self.active.replace(id.index);
+            catch_unwind_hook("stopping", || container.stopping(self, id, reason));
+            self.active = previous;
+
+            // The actor may have been removed by the hook
+            if !self.unborrow(id.index, container) {
+                return Ok(Ok(()));
+            }
+        }
+
+        // Remove children first, so they never outlive their parent
+        let entry = self
+            .actors
+            .get_mut(id.index)
+            .context("failed to find actor")?;
+        let children = std::mem::take(&mut entry.children);
+        for index in children.iter().rev() {
+            let _ = self.remove(Id { index: *index })?;
+        }
+
+        // A borrowed child is only removed once it's returned, wait for it to keep it from
+        // outliving its parent
+        let borrowed: Vec<_> = children
+            .into_iter()
+            .filter(|index| self.actors.contains(*index))
+            .collect();
+        if !borrowed.is_empty() {
+            let entry = self
+                .actors
+                .get_mut(id.index)
+                .context("failed to find actor")?;
+            entry.children.extend(borrowed);
+            entry.removing = Some(reason);
+            return Ok(Ok(()));
+        }
+
+        // Pending messages won't be delivered anymore
+        self.drop_pending(id.index)?;
+
+        // Remove the actor itself
+        let entry = self
+            .actors
+            .remove(id.index)
+            .context("failed to find actor")?;
+
+        // Detach from the parent
+        if let Some(parent) = entry.parent.and_then(|i| self.actors.get_mut(i)) {
+            parent.children.retain(|i| *i != id.index);
+        }
+
+        event!(Level::DEBUG, name = entry.name, "removed actor");
+        self.observe(|o| o.removed(id, entry.name, reason));
+
+        // Other actors shouldn't find it anymore
+        self.unregister_all(entry.registrations);
+
+        // Notify monitors that the actor has stopped
+        let notice = Terminated { id, reason };
+        for monitor in entry.monitors {
+            let _ = self.as_runtime(|rt| monitor.send(rt, notice))?;
+        }
+
+        if let Some(mut container) = entry.container {
+            catch_unwind_hook("stopped", || container.stopped());
+        }
+
+        // The parent may have been waiting for this actor, to finish its own removal
+        if let Some(parent) = entry.parent {
+            self.resume_removal(parent)?;
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Send a message to an actor.
+    #[instrument("Runtime::send", level = "debug", skip_all)]
+    pub fn send<M>(&mut self, id: Id, message: M) -> Result<Result<(), SendError>, InternalError>
+    where
+        M: 'static,
+    {
+        event!(Level::TRACE, "sending message to actor");
+
+        // Validate actor exists
+        let Some(entry) = self.actors.get_mut(id.index) else {
+            self.dead_letter(id, message, DeadLetterReason::NotFound)?;
+            return Ok(Err(SendError::NotFound));
+        };
+        let options = entry.options;
+        let Some(queue) = entry.queue.as_mut() else {
+            self.dead_letter(id, message, DeadLetterReason::NotStarted)?;
+            return Ok(Err(SendError::NotStarted));
+        };
+
+        // Check the message is the right type for the actor
+        let Some(queue) = queue.as_any_mut().downcast_mut::<VecDeque<M>>() else {
+            self.dead_letter(id, message, DeadLetterReason::WrongType)?;
+            return Ok(Err(SendError::WrongType));
+        };
+
+        // Apply the capacity, if the actor has one
+        let mut dropped = None;
+        if let Some(capacity) = options.capacity {
+            if queue.len() >= capacity {
+                match options.overflow {
+                    Overflow::Reject => {
+                        self.dead_letter(id, message, DeadLetterReason::Full)?;
+                        return Ok(Err(SendError::Full));
+                    }
+                    Overflow::DropOldest if capacity > 0 => dropped = queue.pop_front(),
+                    _ => {
+                        self.dead_letter(id, message, DeadLetterReason::Dropped)?;
+                        return Ok(Ok(()));
+                    }
+                }
+            }
+        }
+
+        if let Some(observer) = &mut self.observer {
+            observer.sent(id, std::any::type_name::<M>(), &message);
+        }
+        if self.recorder.is_recording() {
+            self.recorder
+                .record_message(id, self.active, entry.name, &message);
+        }
+
+        queue.push_back(message);
+        entry.stats.received += 1;
+
+        // Schedule the actor, unless it's already scheduled
+        if !entry.queued {
+            entry.queued = true;
+            self.queue.push_back(id.index);
+        }
+
+        if let Some(dropped) = dropped {
+            self.dead_letter(id, dropped, DeadLetterReason::Dropped)?;
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Take the next pending message of an actor.
+    pub(crate) fn pop_message<M>(&mut self, id: Id) -> Option<M>
+    where
+        M: 'static,
+    {
+        let entry = self.actors.get_mut(id.index)?;
+        let queue = entry.queue.as_mut()?.as_any_mut();
+        let queue = queue.downcast_mut::<VecDeque<M>>()?;
+        let message = queue.pop_front()?;
+
+        self.wake_capacity_watchers(id.index);
+
+        Some(message)
+    }
+
+    pub(crate) fn add_ask(&mut self, ask: Box<dyn PendingAsk>) {
+        self.asks.push(ask);
+    }
+
+    /// Create a mailbox owned by `owner`, that signals it when it receives a message.
+    ///
+    /// The owner should have `Signal` as its message type. See `Mailbox` for more information.
+    pub fn mailbox<T>(&self, owner: Id) -> Result<Mailbox<T>, InternalError>
+    where
+        T: 'static,
+    {
+        if !self.actors.contains(owner.index) {
+            return Err(anyhow!("failed to find actor").into());
+        }
+
+        Ok(Mailbox::new(owner))
+    }
+
+    /// Set the maximum amount of messages an actor processes before other actors get a turn.
+    ///
+    /// An actor that still has messages left after its budget runs out is scheduled again, behind
+    /// all other scheduled actors.
+    /// At least one message is always processed per turn.
+    /// By default, an actor processes all its pending messages in one turn.
+    pub fn set_activation_budget(&mut self, budget: Option<usize>) {
+        self.activation_budget = budget;
+    }
+
+    /// Set the maximum amount of messages processed in one call to `process`.
+    ///
+    /// When the budget runs out, `process` returns early, and `has_pending` returns true.
+    /// By default, `process` continues until no messages are left.
+    pub fn set_process_budget(&mut self, budget: Option<usize>) {
+        self.process_budget = budget;
+    }
+
+    /// Check if any actors have pending messages left to process.
+    ///
+    /// Timers that aren't due yet don't count, use `next_deadline` for those.
+    pub fn has_pending(&self) -> bool {
+        self.queue.iter().any(|i| self.actors.contains(*i))
+    }
+
+    /// Process all pending signalled actors, until none are left pending, or the process budget
+    /// runs out.
+    ///
+    /// Timers that are due are fired first.
+    /// Timers that become due while processing are left for the next call.
+    #[instrument("Runtime::process", level = "debug", skip_all)]
+    pub fn process(&mut self) -> Result<(), ProcessError> {
+        let messages = self.process_budget.unwrap_or(usize::MAX);
+        self.process_with(Budget::new(messages, None), ProcessCall::All)?;
+
+        Ok(())
+    }
+
+    /// Process pending actors until none are left pending, or `duration` has passed.
+    ///
+    /// The deadline is checked between messages, so a slow message can still overrun it.
+    /// Returns true if work is still pending.
+    #[instrument("Runtime::process_for", level = "debug", skip_all)]
+    pub fn process_for(&mut self, duration: Duration) -> Result<bool, ProcessError> {
+        let deadline = Instant::now() + duration;
+        self.process_with(
+            Budget::new(usize::MAX, Some(deadline)),
+            ProcessCall::Timed(0),
+        )
+    }
+
+    /// Process at most `count` pending messages.
+    ///
+    /// This ignores the process budget, but still respects the activation budget.
+    /// Returns true if work is still pending.
+    #[instrument("Runtime::process_n", level = "debug", skip_all)]
+    pub fn process_n(&mut self, count: usize) -> Result<bool, ProcessError> {
+        self.process_with(Budget::new(count, None), ProcessCall::Count(count))
+    }
+
+    /// Process at most one pending message.
+    ///
+    /// Returns true if work is still pending.
+    #[instrument("Runtime::process_one", level = "debug", skip_all)]
+    pub fn process_one(&mut self) -> Result<bool, ProcessError> {
+        self.process_with(Budget::new(1, None), ProcessCall::Count(1))
+    }
+
+    fn process_with(
+        &mut self,
+        mut budget: Budget,
+        call: ProcessCall,
+    ) -> Result<bool, ProcessError> {
+        let messages = budget.remaining();
+        let recorded = self.recorder.record_process(call);
+        let result = self.process_inner(&mut budget);
+
+        // Time isn't reproducible, so timed processing is recorded by how much it got done
+        if let (Some(index), ProcessCall::Timed(_)) = (recorded, call) {
+            let processed = messages - budget.remaining();
+            self.recorder
+                .set_process_call(index, ProcessCall::Timed(processed));
+        }
+
+        // Include the seed, so a failure with random scheduling can be reproduced
+        let seed = self.scheduling_seed();
+        result.map_err(|error| ProcessError { seed, ..error })
+    }
+
+    fn process_inner(&mut self, budget: &mut Budget) -> Result<bool, ProcessError> {
+        if let Err(error) = self.fire_timers(self.now()) {
+            let error = ProcessError::internal(None, None, error);
+            self.handle_internal_error(error)?;
+        }
+
+        let activation_budget = self.activation_budget.unwrap_or(usize::MAX).max(1);
+
+        while budget.available() {
+            // Failed asks send messages, so check them before deciding we're done
+            if let Err(error) = self.poll_asks() {
+                let error = ProcessError::internal(None, None, error);
+                self.handle_internal_error(error)?;
+            }
+
+            if self.queue.is_empty() {
+                break;
+            }
+
+            while budget.available() {
+                let Some(index) = self.next_scheduled() else {
+                    break;
+                };
+
+                // Removed actors are skipped here, rather than searching the queue when removing
+                let Some(entry) = self.actors.get_mut(index) else {
+                    continue;
+                };
+                entry.queued = false;
+                let pending = entry.queue.as_ref().map(|q| q.len()).unwrap_or(0);
+
+                let limit = self.activation_limit(activation_budget, pending);
+                let mut activation = budget.split(limit);
+                let result = self.process_actor(index, &mut activation);
+                budget.join(limit, activation);
+
+                // Actor errors have already been handled by policy, only aborts make it here
+                match result {
+                    Err(error) if error.kind() == ProcessErrorKind::Internal => {
+                        self.handle_internal_error(error)?;
+                    }
+                    result => result?,
+                }
+            }
+        }
+
+        Ok(self.has_pending())
+    }
+
+    fn poll_asks(&mut self) -> Result<(), InternalError> {
+        let asks = std::mem::take(&mut self.asks);
+        let mut remaining = Vec::new();
+
+        for ask in asks {
+            if !self.as_runtime(|rt| ask.poll(rt))? {
+                remaining.push(ask);
+            }
+        }
+
+        // Keep any asks added while polling
+        remaining.append(&mut self.asks);
+        self.asks = remaining;
+
+        Ok(())
+    }
+
+    fn process_actor(&mut self, index: Index, budget: &mut Budget) -> Result<(), ProcessError> {
+        let id = Id { index };
+        let (name, mut container) = self
+            .borrow(index)
+            .map_err(|e| ProcessError::internal(Some(id), None, e))?;
+
+        // TODO: Re-think our usage of tracing, we maybe should use an actor-native logging system.
+        let span = span!(Level::INFO, "actor", name);
+        let _entered = span.enter();
+
+        // Let the actor's implementation process, a panic is handled the same as an error
+        event!(Level::TRACE, "calling actor");
+        self.observe(|o| o.activation_started(id, name));
+        let remaining = budget.remaining();
+        let start = Instant::now();
+        let previous = self.active.replace(index);
+        let result = catch_unwind(|| container.process(self, id, budget));
+        self.active = previous;
+        let elapsed = start.elapsed();
+        let processed = remaining - budget.remaining();
+        self.observe(|o| o.activation_ended(id, name, processed, elapsed));
+
+        // Return the actor now that we're done with it, unless it was removed while processing
+        if !self.unborrow(index, container) {
+            return Ok(());
+        }
+        self.record_activation(index, processed, elapsed);
+
+        // If it was removed while processing, it can be removed for real now that it's returned
+        let removed = self
+            .finish_removal(index)
+            .map_err(|e| ProcessError::internal(Some(id), Some(name), e))?;
+        if removed {
+            if let Err(error) = result {
+                event!(
+                    Level::ERROR,
+                    name,
+                    "actor failed while removed:\n{:?}",
+                    error
+                );
+            }
+
+            return Ok(());
+        }
+
+        // Same for restarting, the returned instance is replaced, whatever it returned
+        let restarted = self
+            .finish_restart(index)
+            .map_err(|e| ProcessError::internal(Some(id), Some(name), e))?;
+        if restarted {
+            if let Err(error) = result {
+                event!(
+                    Level::ERROR,
+                    name,
+                    "actor failed while restarted:\n{:?}",
+                    error
+                );
+            }
+
+            return Ok(());
+        }
+
+        match result {
+            // Stop if necessary
+            Ok(flow) if flow.is_break() => {
+                span!(Level::DEBUG, "actor control flow break");
+                self.remove_with_reason(id, StopReason::Break)
+                    .map_err(|e| ProcessError::internal(Some(id), Some(name), e))?
+                    .map_err(|e| ProcessError::internal(Some(id), Some(name), e))?;
+                return Ok(());
+            }
+            Ok(_) => {}
+            // If an error happened, handle it according to the runtime's policy
+            Err(error) => self.handle_actor_error(index, error)?,
+        }
+
+        // If the budget ran out before the actor was done, let it continue after everyone else
+        self.reschedule(index);
+
+        Ok(())
+    }
+
+    fn reschedule(&mut self, index: Index) {
+        let Some(entry) = self.actors.get_mut(index) else {
+            return;
+        };
+
+        let has_messages = entry.queue.as_ref().map(|q| !q.is_empty()).unwrap_or(false);
+        if has_messages && !entry.queued {
+            entry.queued = true;
+            self.queue.push_back(index);
+        }
+    }
+
+    fn borrow(
+        &mut self,
+        index: Index,
+    ) -> Result<(&'static str, Box<dyn AnyActorContainer>), InternalError> {
+        let entry = self.actors.get_mut(index).context("failed to find actor")?;
+        let container = entry
+            .container
+            .take()
+            .context("expected container not available")?;
+
+        Ok((entry.name, container))
+    }
+
+    fn unborrow(&mut self, index: Index, container: Box<dyn AnyActorContainer>) -> bool {
+        let Some(entry) = self.actors.get_mut(index) else {
+            return false;
+        };
+        entry.container = Some(container);
+
+        true
+    }
+
+    /// Finish removing an actor that was removed while borrowed, returning true if it was.
+    fn finish_removal(&mut self, index: Index) -> Result<bool, InternalError> {
+        let Some(reason) = self.actors.get_mut(index).and_then(|e| e.removing.take()) else {
+            return Ok(false);
+        };
+
+        let _ = self.remove_with_reason(Id { index }, reason)?;
+        Ok(true)
+    }
+
+    /// Continue removing an actor that was waiting for its borrowed children, if they're gone.
+    fn resume_removal(&mut self, index: Index) -> Result<(), InternalError> {
+        let Some(entry) = self.actors.get(index) else {
+            return Ok(());
+        };
+        let Some(reason) = entry.removing else {
+            return Ok(());
+        };
+
+        // Still borrowed itself, or still waiting for other children
+        if entry.container.is_none() || !entry.children.is_empty() {
+            return Ok(());
+        }
+
+        let _ = self.remove_with_reason(Id { index }, reason)?;
+        Ok(())
+    }
+}
+
+/// Identifier of an actor inserted into a runtime.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Id {
+    index: Index,
+}
+
+/// Failed to remove actor.
+#[derive(Error, Debug)]
+pub enum RemoveError {
+    /// No actor found for id.
+    #[error("no actor found for id")]
+    NotFound,
+}
+
+/// Failed to send message to actor.
+#[derive(Error, Debug)]
+pub enum SendError {
+    /// No actor found for id.
+    #[error("no actor found for id")]
+    NotFound,
+
+    /// Message wrong type for actor.
+    #[error("message wrong type for actor")]
+    WrongType,
+
+    /// Actor has been created, but not started yet.
+    #[error("actor not started yet")]
+    NotStarted,
+
+    /// Actor's mailbox is at capacity.
+    #[error("actor's mailbox is at capacity")]
+    Full,
+}
+
+/// Failed to process actors.
+///
+/// Returned by `process` when an error's policy is `ErrorPolicy::Abort`.
+#[derive(Debug)]
+pub struct ProcessError {
+    id: Option<Id>,
+    name: Option<&'static str>,
+    kind: ProcessErrorKind,
+    seed: Option<u64>,
+    source: Error,
+}
+
+impl ProcessError {
+    fn actor(id: Id, name: Option<&'static str>, source: ActorError) -> Self {
+        Self {
+            id: Some(id),
+            name,
+            kind: ProcessErrorKind::Actor,
+            seed: None,
+            source: source.into(),
+        }
+    }
+
+    fn internal(id: Option<Id>, name: Option<&'static str>, source: impl Into<Error>) -> Self {
+        Self {
+            id,
+            name,
+            kind: ProcessErrorKind::Internal,
+            seed: None,
+            source: source.into(),
+        }
+    }
+
+    /// Id of the actor involved, if any.
+    pub fn id(&self) -> Option<Id> {
+        self.id
+    }
+
+    /// Name of the actor involved, if known.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> ProcessErrorKind {
+        self.kind
+    }
+
+    /// Seed of random scheduling when the error happened, if it was enabled.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl Display for ProcessError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.kind, self.name) {
+            (ProcessErrorKind::Actor, Some(name)) => write!(f, "actor \"{}\" failed", name)?,
+            (ProcessErrorKind::Actor, None) => write!(f, "actor failed")?,
+            (ProcessErrorKind::Internal, Some(name)) => {
+                write!(f, "internal error while processing actor \"{}\"", name)?
+            }
+            (ProcessErrorKind::Internal, None) => write!(f, "internal error while processing")?,
+        }
+
+        if let Some(seed) = self.seed {
+            write!(f, " (scheduling seed {})", seed)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ProcessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Kind of error that caused processing to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessErrorKind {
+    /// An actor returned an error, or panicked.
+    Actor,
+    /// Stewart failed internally.
+    Internal,
+}
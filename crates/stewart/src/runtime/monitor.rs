@@ -0,0 +1,59 @@
+use thiserror::Error;
+use tracing::{event, instrument, Level};
+
+use crate::runtime::{Id, Runtime};
+use crate::sender::Sender;
+use crate::InternalError;
+
+/// Notice that a monitored actor has stopped.
+#[derive(Debug, Clone, Copy)]
+pub struct Terminated {
+    /// The actor that stopped.
+    pub id: Id,
+    /// Why the actor stopped.
+    pub reason: StopReason,
+}
+
+/// Reason an actor stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The actor returned `ControlFlow::Break`.
+    Break,
+    /// The actor returned an `ActorError`, and wasn't restarted by a supervisor.
+    Error,
+    /// The actor was removed using `Runtime::remove`, or because its parent was removed.
+    Removed,
+    /// The actor is being replaced by a new instance by its supervisor.
+    ///
+    /// This is only given to the `Actor::stopping` hook, monitors are not notified of restarts.
+    Restart,
+}
+
+impl Runtime {
+    /// Monitor an actor, sending a `Terminated` notice to `watcher` when it stops.
+    ///
+    /// Restarts by a supervisor don't count as stopping, as the actor keeps its `Id`.
+    #[instrument("Runtime::monitor", level = "debug", skip_all)]
+    pub fn monitor(
+        &mut self,
+        watcher: Sender<Terminated>,
+        target: Id,
+    ) -> Result<Result<(), MonitorError>, InternalError> {
+        event!(Level::DEBUG, "monitoring actor");
+
+        let Some(entry) = self.actors.get_mut(target.index) else {
+            return Ok(Err(MonitorError::NotFound));
+        };
+        entry.monitors.push(watcher);
+
+        Ok(Ok(()))
+    }
+}
+
+/// Failed to monitor actor.
+#[derive(Error, Debug)]
+pub enum MonitorError {
+    /// No actor found for id.
+    #[error("no actor found for id")]
+    NotFound,
+}
@@ -0,0 +1,67 @@
+use std::any::Any;
+use std::time::Duration;
+
+use crate::runtime::{Id, ProcessError, Runtime, StopReason};
+use crate::ActorError;
+
+/// Callbacks for observing what happens in a runtime.
+///
+/// Useful for metrics, custom logging, recording, and test assertions.
+/// All methods default to doing nothing, so only what's needed has to be implemented.
+///
+/// Observers don't get access to the runtime, they're called in the middle of runtime operations.
+pub trait RuntimeObserver {
+    /// Called after an actor entry has been created.
+    fn inserted(&mut self, id: Id, name: &'static str) {
+        let _ = (id, name);
+    }
+
+    /// Called after an actor has been removed.
+    fn removed(&mut self, id: Id, name: &'static str, reason: StopReason) {
+        let _ = (id, name, reason);
+    }
+
+    /// Called when a message is about to be queued for an actor.
+    ///
+    /// Messages that can't be delivered are not observed here, see `Runtime::set_dead_letters`.
+    fn sent(&mut self, target: Id, type_name: &'static str, message: &dyn Any) {
+        let _ = (target, type_name, message);
+    }
+
+    /// Called before an actor processes its pending messages.
+    fn activation_started(&mut self, id: Id, name: &'static str) {
+        let _ = (id, name);
+    }
+
+    /// Called after an actor processed its pending messages, with how many and how long it took.
+    fn activation_ended(&mut self, id: Id, name: &'static str, processed: usize, time: Duration) {
+        let _ = (id, name, processed, time);
+    }
+
+    /// Called when an actor returns an error, or panics, before the error policy is applied.
+    fn actor_failed(&mut self, id: Id, name: Option<&'static str>, error: &ActorError) {
+        let _ = (id, name, error);
+    }
+
+    /// Called when stewart fails internally, before the error policy is applied.
+    fn internal_error(&mut self, error: &ProcessError) {
+        let _ = error;
+    }
+}
+
+impl Runtime {
+    /// Set the observer of the runtime, or `None` to remove it.
+    pub fn set_observer(&mut self, observer: Option<Box<dyn RuntimeObserver>>) {
+        self.observer = observer;
+    }
+
+    /// Call the observer, if there is one.
+    pub(super) fn observe<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut dyn RuntimeObserver),
+    {
+        if let Some(observer) = &mut self.observer {
+            f(observer.as_mut());
+        }
+    }
+}
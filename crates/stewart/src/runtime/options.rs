@@ -0,0 +1,61 @@
+use anyhow::Context;
+use tracing::instrument;
+
+use crate::runtime::{Id, Runtime};
+use crate::{Actor, Addr, InternalError};
+
+/// Configuration of an actor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ActorOptions {
+    /// Maximum amount of pending messages, `None` for unbounded.
+    pub capacity: Option<usize>,
+    /// What to do with messages sent while the actor's mailbox is at capacity.
+    pub overflow: Overflow,
+}
+
+/// Policy for messages sent to an actor with a full mailbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Reject the message, failing the send with `SendError::Full`.
+    #[default]
+    Reject,
+    /// Drop the oldest pending message to make room.
+    DropOldest,
+    /// Silently drop the message being sent.
+    DropNewest,
+}
+
+impl Runtime {
+    /// Insert an actor into the runtime, with the given options.
+    ///
+    /// See `insert` for more information.
+    #[instrument("Runtime::insert_with", level = "debug", skip_all)]
+    pub fn insert_with<A>(
+        &mut self,
+        name: &'static str,
+        actor: A,
+        options: ActorOptions,
+    ) -> Result<Addr<A>, InternalError>
+    where
+        A: Actor,
+    {
+        let id = self.create(name)?;
+        self.set_options(id, options)?;
+        self.start(id, actor)
+    }
+
+    /// Set the options of an actor.
+    ///
+    /// Typically this is done after `create`, before the actor is started.
+    /// Changing the capacity doesn't drop messages already pending.
+    #[instrument("Runtime::set_options", level = "debug", skip_all)]
+    pub fn set_options(&mut self, id: Id, options: ActorOptions) -> Result<(), InternalError> {
+        let entry = self
+            .actors
+            .get_mut(id.index)
+            .context("failed to find actor")?;
+        entry.options = options;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use thunderdome::Index;
+use tracing::{event, Level};
+
+use crate::runtime::{Clock, Id, ProcessError, Runtime, RuntimeObserver, StopReason};
+use crate::ActorError;
+
+/// What to do when an error happens while processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Log the error, and continue as if nothing happened.
+    Continue,
+    /// Log the error, and remove the actor involved.
+    Remove,
+    /// Handle the error as a failure of the actor involved.
+    ///
+    /// Supervised actors are restarted by their supervisor, other actors are removed.
+    Escalate,
+    /// Stop processing, and return the error from `process`.
+    Abort,
+}
+
+/// Error policies of a runtime.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Policies {
+    pub actor_errors: ErrorPolicy,
+    pub internal_errors: ErrorPolicy,
+}
+
+impl Default for Policies {
+    fn default() -> Self {
+        Self {
+            actor_errors: ErrorPolicy::Escalate,
+            internal_errors: ErrorPolicy::Abort,
+        }
+    }
+}
+
+/// Builder for configuring a `Runtime`.
+///
+/// `Runtime::default()` is the same as building with default settings.
+#[derive(Default)]
+pub struct RuntimeBuilder {
+    policies: Policies,
+    activation_budget: Option<usize>,
+    process_budget: Option<usize>,
+    observer: Option<Box<dyn RuntimeObserver>>,
+    clock: Option<Box<dyn Clock>>,
+    seed: Option<u64>,
+    shuffle_activations: bool,
+}
+
+impl RuntimeBuilder {
+    /// Set what happens when an actor returns an error, or panics.
+    ///
+    /// Defaults to `ErrorPolicy::Escalate`.
+    pub fn actor_errors(mut self, policy: ErrorPolicy) -> Self {
+        self.policies.actor_errors = policy;
+        self
+    }
+
+    /// Set what happens when stewart fails internally while processing.
+    ///
+    /// Defaults to `ErrorPolicy::Abort`.
+    pub fn internal_errors(mut self, policy: ErrorPolicy) -> Self {
+        self.policies.internal_errors = policy;
+        self
+    }
+
+    /// Set the activation budget, see `Runtime::set_activation_budget`.
+    pub fn activation_budget(mut self, budget: usize) -> Self {
+        self.activation_budget = Some(budget);
+        self
+    }
+
+    /// Set the process budget, see `Runtime::set_process_budget`.
+    pub fn process_budget(mut self, budget: usize) -> Self {
+        self.process_budget = Some(budget);
+        self
+    }
+
+    /// Set the observer, see `Runtime::set_observer`.
+    pub fn observer(mut self, observer: impl RuntimeObserver + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Set the clock, see `Runtime::set_clock`.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Process actors in a random order determined by `seed`, see `Runtime::set_random_scheduling`.
+    pub fn random_scheduling(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// With random scheduling, also randomize how many messages actors process at a time.
+    pub fn shuffle_activations(mut self, shuffle: bool) -> Self {
+        self.shuffle_activations = shuffle;
+        self
+    }
+
+    /// Build the runtime.
+    pub fn build(self) -> Runtime {
+        let mut rt = Runtime::default();
+        rt.policies = self.policies;
+        rt.activation_budget = self.activation_budget;
+        rt.process_budget = self.process_budget;
+        rt.observer = self.observer;
+        rt.clock = self.clock;
+        rt.set_random_scheduling(self.seed, self.shuffle_activations);
+        rt
+    }
+}
+
+impl Runtime {
+    /// Create a builder for configuring a new runtime.
+    pub fn builder() -> RuntimeBuilder {
+        RuntimeBuilder::default()
+    }
+
+    /// Handle an actor returning an error, according to the actor error policy.
+    pub(super) fn handle_actor_error(
+        &mut self,
+        index: Index,
+        error: ActorError,
+    ) -> Result<(), ProcessError> {
+        let id = Id { index };
+        let name = self.actors.get(index).map(|e| e.name);
+        let seed = self.scheduling_seed();
+        event!(Level::ERROR, name, seed, "actor failed:\n{:?}", error);
+        self.record_error(index);
+        self.observe(|o| o.actor_failed(id, name, &error));
+
+        match self.policies.actor_errors {
+            ErrorPolicy::Continue => {}
+            ErrorPolicy::Remove => {
+                self.remove_with_reason(id, StopReason::Error)
+                    .map_err(|e| ProcessError::internal(Some(id), name, e))?
+                    .map_err(|e| ProcessError::internal(Some(id), name, e))?;
+            }
+            ErrorPolicy::Escalate => {
+                self.handle_failure(index)
+                    .map_err(|e| ProcessError::internal(Some(id), name, e))?;
+            }
+            ErrorPolicy::Abort => return Err(ProcessError::actor(id, name, error)),
+        }
+
+        Ok(())
+    }
+
+    /// Handle an internal error, according to the internal error policy.
+    pub(super) fn handle_internal_error(
+        &mut self,
+        error: ProcessError,
+    ) -> Result<(), ProcessError> {
+        event!(Level::ERROR, "internal error:\n{:?}", error);
+        self.observe(|o| o.internal_error(&error));
+
+        let policy = self.policies.internal_errors;
+        let Some(id) = error.id() else {
+            // Without an actor involved, there's nothing to remove or escalate to
+            if policy == ErrorPolicy::Abort {
+                return Err(error);
+            }
+
+            return Ok(());
+        };
+
+        match policy {
+            ErrorPolicy::Continue => {}
+            ErrorPolicy::Remove => {
+                // The actor may already be gone, depending on what failed
+                if let Err(error) = self.remove_with_reason(id, StopReason::Error) {
+                    event!(Level::ERROR, "failed to remove actor:\n{:?}", error);
+                }
+            }
+            ErrorPolicy::Escalate => {
+                if self.actors.contains(id.index) {
+                    self.handle_failure(id.index)
+                        .map_err(|e| ProcessError::internal(Some(id), error.name(), e))?;
+                }
+            }
+            ErrorPolicy::Abort => return Err(error),
+        }
+
+        Ok(())
+    }
+}
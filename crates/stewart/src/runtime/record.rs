@@ -0,0 +1,407 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use thiserror::Error;
+use thunderdome::Index;
+use tracing::{event, Level};
+
+use crate::runtime::{Id, Runtime};
+
+/// Message type that can be serialized, so its payload can be recorded and replayed.
+///
+/// Implemented for common primitive types, implement it for your own message types to opt them
+/// in to payload recording with `Runtime::record_payloads`.
+pub trait Recordable: Sized + 'static {
+    /// Serialize the message, appending it to `buffer`.
+    fn encode(&self, buffer: &mut Vec<u8>);
+
+    /// Deserialize a message previously serialized with `encode`.
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError>;
+}
+
+/// Failed to decode recorded data.
+#[derive(Error, Debug)]
+#[error("failed to decode recorded data")]
+pub struct DecodeError;
+
+/// Session of messages recorded from a runtime.
+#[derive(Debug, Default, Clone)]
+pub struct Recording {
+    /// Recorded events, in the order they happened.
+    pub events: Vec<RecordedEvent>,
+}
+
+/// Event recorded from a runtime.
+#[derive(Debug, Clone)]
+pub enum RecordedEvent {
+    /// A message was queued for an actor.
+    Message(RecordedMessage),
+    /// The runtime started processing, with the call it was processed with.
+    Process(ProcessCall),
+}
+
+/// Call made to process a runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessCall {
+    /// `Runtime::process`, limited by the runtime's process budget.
+    All,
+    /// `Runtime::process_n` or `Runtime::process_one`, with the amount of messages allowed.
+    Count(usize),
+    /// `Runtime::process_for`, with the amount of messages processed before its deadline.
+    ///
+    /// Time isn't reproducible, so this is replayed as `Runtime::process_n` with that amount.
+    Timed(usize),
+}
+
+/// Message recorded from a runtime.
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    /// The actor the message was sent to.
+    pub target: Id,
+    /// Where the message was sent from.
+    pub origin: MessageOrigin,
+    /// Name of the target actor.
+    pub name: String,
+    /// Name of the message's type.
+    pub type_name: String,
+    /// Serialized message, if its type was opted in with `Runtime::record_payloads`.
+    pub payload: Option<Vec<u8>>,
+}
+
+/// Where a recorded message was sent from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageOrigin {
+    /// Sent from outside the runtime, these are the messages that get replayed.
+    External,
+    /// Sent by an actor, while it was being called.
+    Actor(Id),
+    /// Sent by the runtime itself, such as timer messages, ask failures, monitor and capacity
+    /// notices, and dead letters.
+    Runtime,
+}
+
+type Encoder = fn(&dyn Any, &mut Vec<u8>);
+
+#[derive(Default)]
+pub(super) struct Recorder {
+    encoders: HashMap<TypeId, Encoder>,
+    recording: Option<Recording>,
+    /// Set while the runtime itself is sending messages.
+    runtime_origin: bool,
+}
+
+impl Recorder {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn record_message<M>(
+        &mut self,
+        target: Id,
+        active: Option<Index>,
+        name: &'static str,
+        message: &M,
+    ) where
+        M: 'static,
+    {
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+
+        let payload = self.encoders.get(&TypeId::of::<M>()).map(|encode| {
+            let mut buffer = Vec::new();
+            encode(message, &mut buffer);
+            buffer
+        });
+
+        let origin = match (self.runtime_origin, active) {
+            (true, _) => MessageOrigin::Runtime,
+            (false, Some(index)) => MessageOrigin::Actor(Id { index }),
+            (false, None) => MessageOrigin::External,
+        };
+
+        let message = RecordedMessage {
+            target,
+            origin,
+            name: name.to_string(),
+            type_name: std::any::type_name::<M>().to_string(),
+            payload,
+        };
+        recording.events.push(RecordedEvent::Message(message));
+    }
+
+    /// Record a process call, returning the index of the event if recording.
+    pub fn record_process(&mut self, call: ProcessCall) -> Option<usize> {
+        let recording = self.recording.as_mut()?;
+        recording.events.push(RecordedEvent::Process(call));
+        Some(recording.events.len() - 1)
+    }
+
+    /// Replace a previously recorded process call.
+    pub fn set_process_call(&mut self, index: usize, call: ProcessCall) {
+        let event = self
+            .recording
+            .as_mut()
+            .and_then(|recording| recording.events.get_mut(index));
+
+        if let Some(RecordedEvent::Process(recorded)) = event {
+            *recorded = call;
+        }
+    }
+}
+
+impl Runtime {
+    /// Call `f`, recording messages sent by it as sent by the runtime itself.
+    pub(super) fn as_runtime<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = std::mem::replace(&mut self.recorder.runtime_origin, true);
+        let result = f(self);
+        self.recorder.runtime_origin = previous;
+        result
+    }
+
+    /// Start recording every message queued for an actor, and every call to process.
+    ///
+    /// Messages are recorded with their target, source, and type, but without their payload,
+    /// unless their type is opted in with `record_payloads`.
+    /// Messages that can't be delivered aren't recorded.
+    pub fn start_recording(&mut self) {
+        event!(Level::INFO, "starting recording");
+        self.recorder.recording = Some(Recording::default());
+    }
+
+    /// Stop recording, returning what was recorded, or `None` if not recording.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recorder.recording.take()
+    }
+
+    /// Record the payload of messages of type `M`, in addition to their metadata.
+    pub fn record_payloads<M>(&mut self)
+    where
+        M: Recordable,
+    {
+        fn encode<M: Recordable>(message: &dyn Any, buffer: &mut Vec<u8>) {
+            if let Some(message) = message.downcast_ref::<M>() {
+                message.encode(buffer);
+            }
+        }
+
+        self.recorder
+            .encoders
+            .insert(TypeId::of::<M>(), encode::<M>);
+    }
+}
+
+impl Recording {
+    /// Serialize the recording, for example to store it in a file.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(MAGIC);
+
+        for event in &self.events {
+            match event {
+                RecordedEvent::Process(call) => {
+                    buffer.push(TAG_PROCESS);
+                    let (kind, count) = match call {
+                        ProcessCall::All => (0, 0),
+                        ProcessCall::Count(count) => (1, *count),
+                        ProcessCall::Timed(count) => (2, *count),
+                    };
+                    buffer.push(kind);
+                    (count as u64).encode(&mut buffer);
+                }
+                RecordedEvent::Message(message) => {
+                    buffer.push(TAG_MESSAGE);
+                    message.target.index.to_bits().encode(&mut buffer);
+                    match message.origin {
+                        MessageOrigin::External => buffer.push(0),
+                        MessageOrigin::Actor(source) => {
+                            buffer.push(1);
+                            source.index.to_bits().encode(&mut buffer);
+                        }
+                        MessageOrigin::Runtime => buffer.push(2),
+                    }
+                    encode_bytes(message.name.as_bytes(), &mut buffer);
+                    encode_bytes(message.type_name.as_bytes(), &mut buffer);
+                    match &message.payload {
+                        Some(payload) => {
+                            buffer.push(1);
+                            encode_bytes(payload, &mut buffer);
+                        }
+                        None => buffer.push(0),
+                    }
+                }
+            }
+        }
+
+        buffer
+    }
+
+    /// Deserialize a recording previously serialized with `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = Reader::new(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(DecodeError);
+        }
+
+        let mut events = Vec::new();
+        while !reader.is_empty() {
+            let event = match reader.take(1)?[0] {
+                TAG_PROCESS => {
+                    let kind = reader.take(1)?[0];
+                    let count = u64::decode(reader.take(8)?)?;
+                    let count = usize::try_from(count).map_err(|_| DecodeError)?;
+
+                    let call = match kind {
+                        0 => ProcessCall::All,
+                        1 => ProcessCall::Count(count),
+                        2 => ProcessCall::Timed(count),
+                        _ => return Err(DecodeError),
+                    };
+                    RecordedEvent::Process(call)
+                }
+                TAG_MESSAGE => {
+                    let target = reader.id()?;
+                    let origin = match reader.take(1)?[0] {
+                        0 => MessageOrigin::External,
+                        1 => MessageOrigin::Actor(reader.id()?),
+                        2 => MessageOrigin::Runtime,
+                        _ => return Err(DecodeError),
+                    };
+                    let name = reader.string()?;
+                    let type_name = reader.string()?;
+                    let payload = match reader.take(1)?[0] {
+                        0 => None,
+                        _ => Some(reader.bytes()?.to_vec()),
+                    };
+
+                    RecordedEvent::Message(RecordedMessage {
+                        target,
+                        origin,
+                        name,
+                        type_name,
+                        payload,
+                    })
+                }
+                _ => return Err(DecodeError),
+            };
+            events.push(event);
+        }
+
+        Ok(Self { events })
+    }
+}
+
+const MAGIC: &[u8] = b"STWREC02";
+const TAG_PROCESS: u8 = 0;
+const TAG_MESSAGE: u8 = 1;
+
+pub(super) fn encode_bytes(bytes: &[u8], buffer: &mut Vec<u8>) {
+    (bytes.len() as u64).encode(buffer);
+    buffer.extend_from_slice(bytes);
+}
+
+/// Reader for decoding recorded data.
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError);
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], DecodeError> {
+        let len = u64::decode(self.take(8)?)?;
+        let len = usize::try_from(len).map_err(|_| DecodeError)?;
+        self.take(len)
+    }
+
+    pub fn string(&mut self) -> Result<String, DecodeError> {
+        String::decode(self.bytes()?)
+    }
+
+    pub fn id(&mut self) -> Result<Id, DecodeError> {
+        let bits = u64::decode(self.take(8)?)?;
+        let index = Index::from_bits(bits).ok_or(DecodeError)?;
+        Ok(Id { index })
+    }
+}
+
+impl Recordable for () {
+    fn encode(&self, _buffer: &mut Vec<u8>) {}
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if !bytes.is_empty() {
+            return Err(DecodeError);
+        }
+
+        Ok(())
+    }
+}
+
+impl Recordable for bool {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.push(*self as u8);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        match bytes {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(DecodeError),
+        }
+    }
+}
+
+macro_rules! impl_recordable_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Recordable for $ty {
+                fn encode(&self, buffer: &mut Vec<u8>) {
+                    buffer.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+                    let bytes = bytes.try_into().map_err(|_| DecodeError)?;
+                    Ok(Self::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_recordable_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl Recordable for String {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError)
+    }
+}
+
+impl Recordable for Vec<u8> {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(bytes.to_vec())
+    }
+}
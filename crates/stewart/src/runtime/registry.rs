@@ -0,0 +1,209 @@
+use std::any::Any;
+use std::collections::HashMap;
+
+use thiserror::Error;
+use thunderdome::Index;
+use tracing::{event, instrument, Level};
+
+use crate::runtime::{Id, Runtime};
+use crate::sender::Sender;
+
+/// Key a sender is registered under.
+///
+/// Keys are either global, or scoped to an actor, typically a parent looking up its children.
+/// A scoped key doesn't conflict with a global key or a key in another scope with the same name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    scope: Option<Id>,
+    name: String,
+}
+
+impl Key {
+    /// Create a global key.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            scope: None,
+            name: name.into(),
+        }
+    }
+
+    /// Create a key scoped to an actor.
+    ///
+    /// Senders registered under the key are removed when the scope actor is removed.
+    pub fn scoped(scope: Id, name: impl Into<String>) -> Self {
+        Self {
+            scope: Some(scope),
+            name: name.into(),
+        }
+    }
+
+    /// Get the actor this key is scoped to, if any.
+    pub fn scope(&self) -> Option<Id> {
+        self.scope
+    }
+
+    /// Get the name of the key.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl From<&str> for Key {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Key {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Registry {
+    entries: HashMap<Key, Registration>,
+}
+
+struct Registration {
+    owner: Index,
+    type_name: &'static str,
+    sender: Box<dyn Any>,
+}
+
+impl Runtime {
+    /// Register `sender` under `key`, so other actors can find it with `lookup`.
+    ///
+    /// The registration is owned by `owner`, and is removed when `owner` is removed.
+    /// Registering again under a key `owner` already holds replaces the sender, so registering
+    /// in `Actor::started` keeps working across restarts.
+    #[instrument("Runtime::register", level = "debug", skip_all)]
+    pub fn register<M>(
+        &mut self,
+        owner: Id,
+        key: impl Into<Key>,
+        sender: Sender<M>,
+    ) -> Result<(), RegisterError>
+    where
+        M: 'static,
+    {
+        let key = key.into();
+        event!(Level::DEBUG, key = key.name(), "registering sender");
+
+        if !self.actors.contains(owner.index) {
+            return Err(RegisterError::NotFound);
+        }
+
+        if let Some(scope) = key.scope {
+            if !self.actors.contains(scope.index) {
+                return Err(RegisterError::NotFound);
+            }
+        }
+
+        if let Some(existing) = self.registry.entries.get(&key) {
+            if existing.owner != owner.index {
+                return Err(RegisterError::Taken);
+            }
+        } else {
+            // Track the key on the actors it depends on, so it can be removed with them
+            self.track_key(owner.index, &key);
+            if let Some(scope) = key.scope.filter(|s| *s != owner) {
+                self.track_key(scope.index, &key);
+            }
+        }
+
+        let registration = Registration {
+            owner: owner.index,
+            type_name: std::any::type_name::<M>(),
+            sender: Box::new(sender),
+        };
+        self.registry.entries.insert(key, registration);
+
+        Ok(())
+    }
+
+    /// Remove the sender registered under `key`.
+    #[instrument("Runtime::unregister", level = "debug", skip_all)]
+    pub fn unregister(&mut self, key: &Key) -> Result<(), LookupError> {
+        let registration = self
+            .registry
+            .entries
+            .remove(key)
+            .ok_or(LookupError::NotFound)?;
+
+        self.untrack_key(registration.owner, key);
+        if let Some(scope) = key.scope {
+            self.untrack_key(scope.index, key);
+        }
+
+        Ok(())
+    }
+
+    /// Find the sender registered under `key`.
+    pub fn lookup<M>(&self, key: &Key) -> Result<Sender<M>, LookupError>
+    where
+        M: 'static,
+    {
+        let registration = self
+            .registry
+            .entries
+            .get(key)
+            .ok_or(LookupError::NotFound)?;
+
+        let Some(sender) = registration.sender.downcast_ref::<Sender<M>>() else {
+            event!(
+                Level::DEBUG,
+                expected = std::any::type_name::<M>(),
+                registered = registration.type_name,
+                "registered sender wrong type",
+            );
+            return Err(LookupError::WrongType);
+        };
+
+        Ok(sender.clone())
+    }
+
+    /// Remove registrations that depend on an actor that's being removed.
+    pub(super) fn unregister_all(&mut self, keys: Vec<Key>) {
+        for key in keys {
+            // The key may have been removed already through its owner or scope
+            let _ = self.unregister(&key);
+        }
+    }
+
+    fn track_key(&mut self, index: Index, key: &Key) {
+        if let Some(entry) = self.actors.get_mut(index) {
+            entry.registrations.push(key.clone());
+        }
+    }
+
+    fn untrack_key(&mut self, index: Index, key: &Key) {
+        if let Some(entry) = self.actors.get_mut(index) {
+            entry.registrations.retain(|k| k != key);
+        }
+    }
+}
+
+/// Failed to register sender.
+#[derive(Error, Debug)]
+pub enum RegisterError {
+    /// No actor found for the owner or scope id.
+    #[error("no actor found for id")]
+    NotFound,
+
+    /// Another actor already registered a sender under the key.
+    #[error("key already taken by another actor")]
+    Taken,
+}
+
+/// Failed to find registered sender.
+#[derive(Error, Debug)]
+pub enum LookupError {
+    /// No sender registered under the key.
+    #[error("no sender registered under key")]
+    NotFound,
+
+    /// Registered sender is the wrong type.
+    #[error("registered sender wrong type")]
+    WrongType,
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+use tracing::{event, instrument, Level};
+
+use crate::runtime::{
+    DecodeError, Id, MessageOrigin, ProcessCall, ProcessError, Recordable, RecordedEvent,
+    RecordedMessage, Recording, Runtime, SendError,
+};
+use crate::InternalError;
+
+type Decoder = fn(&mut Runtime, Id, &[u8]) -> Result<Result<(), SendError>, ReplayError>;
+
+/// Replays a recorded session into a runtime, to reproduce what happened in it.
+///
+/// Only messages sent from outside the runtime are replayed. Messages sent by actors are sent
+/// again by the actors themselves as they process, and messages sent by the runtime, such as
+/// timer messages, are sent again by the runtime. The runtime should be set up the
+/// same way as the recorded one, so actors end up with the same ids.
+#[derive(Default)]
+pub struct Replayer {
+    decoders: HashMap<String, Decoder>,
+}
+
+impl Replayer {
+    /// Create a new replayer, without any message types registered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a message type, so recorded messages of this type can be replayed.
+    ///
+    /// Types are matched by type name, so recordings can be replayed in a different build.
+    pub fn register<M>(&mut self) -> &mut Self
+    where
+        M: Recordable,
+    {
+        fn decode<M: Recordable>(
+            rt: &mut Runtime,
+            target: Id,
+            bytes: &[u8],
+        ) -> Result<Result<(), SendError>, ReplayError> {
+            let message = M::decode(bytes)?;
+            Ok(rt.send(target, message)?)
+        }
+
+        let type_name = std::any::type_name::<M>().to_string();
+        self.decoders.insert(type_name, decode::<M>);
+
+        self
+    }
+
+    /// Replay a recording into a runtime, processing where and how the recorded runtime
+    /// processed.
+    #[instrument("Replayer::replay", level = "debug", skip_all)]
+    pub fn replay(&self, rt: &mut Runtime, recording: &Recording) -> Result<(), ReplayError> {
+        for event in &recording.events {
+            match event {
+                RecordedEvent::Process(ProcessCall::All) => rt.process()?,
+                RecordedEvent::Process(ProcessCall::Count(count) | ProcessCall::Timed(count)) => {
+                    rt.process_n(*count)?;
+                }
+                RecordedEvent::Message(message) => self.replay_message(rt, message)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn replay_message(
+        &self,
+        rt: &mut Runtime,
+        message: &RecordedMessage,
+    ) -> Result<(), ReplayError> {
+        // Actors and the runtime will send the others again themselves
+        if message.origin != MessageOrigin::External {
+            return Ok(());
+        }
+
+        event!(
+            Level::TRACE,
+            type_name = message.type_name,
+            "replaying message"
+        );
+
+        // Make sure we're sending to the same actor, ids are only meaningful in the same setup
+        let info = rt.actor_info(message.target).ok_or(ReplayError::NotFound)?;
+        if info.name != message.name {
+            return Err(ReplayError::NameMismatch {
+                expected: message.name.clone(),
+                found: info.name,
+            });
+        }
+
+        let payload = message
+            .payload
+            .as_ref()
+            .ok_or_else(|| ReplayError::MissingPayload(message.type_name.clone()))?;
+        let decode = self
+            .decoders
+            .get(&message.type_name)
+            .ok_or_else(|| ReplayError::UnknownType(message.type_name.clone()))?;
+
+        decode(rt, message.target, payload)??;
+
+        Ok(())
+    }
+}
+
+/// Failed to replay a recording.
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    /// No actor found for a recorded target id.
+    #[error("no actor found for recorded target")]
+    NotFound,
+
+    /// The actor found for a recorded target id has a different name than recorded.
+    #[error("recorded target was \"{expected}\", found \"{found}\"")]
+    NameMismatch {
+        /// Name of the recorded target.
+        expected: String,
+        /// Name of the actor found with the same id.
+        found: &'static str,
+    },
+
+    /// A message was recorded without its payload.
+    #[error("message of type {0} was recorded without payload")]
+    MissingPayload(String),
+
+    /// A message type wasn't registered with the replayer.
+    #[error("message type {0} not registered")]
+    UnknownType(String),
+
+    /// A recorded payload couldn't be decoded.
+    #[error("failed to decode payload")]
+    Decode(#[from] DecodeError),
+
+    /// A replayed message couldn't be sent.
+    #[error("failed to send message")]
+    Send(#[from] SendError),
+
+    /// Processing failed while replaying.
+    #[error("failed to process")]
+    Process(#[from] ProcessError),
+
+    /// Internal error in stewart.
+    #[error("internal error")]
+    Internal(#[from] InternalError),
+}
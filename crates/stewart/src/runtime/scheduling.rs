@@ -0,0 +1,106 @@
+use thunderdome::Index;
+use tracing::{event, Level};
+
+use crate::runtime::Runtime;
+
+/// Order in which scheduled actors are processed.
+#[derive(Default)]
+pub(super) struct Scheduling {
+    random: Option<Random>,
+}
+
+struct Random {
+    seed: u64,
+    rng: XorShift,
+    shuffle_activations: bool,
+}
+
+impl Runtime {
+    /// Process scheduled actors in a random order, determined by `seed`, or `None` for FIFO order.
+    ///
+    /// Actors should never depend on the order other actors are processed in, but it's easy to
+    /// do so by accident. Randomizing the order helps find these bugs in tests, and the same seed
+    /// always gives the same order, so failures can be reproduced.
+    /// The seed is included in errors returned from processing.
+    ///
+    /// If `shuffle_activations` is set, actors also process a random amount of their pending
+    /// messages at a time, within the activation budget, interleaving them with other actors.
+    pub fn set_random_scheduling(&mut self, seed: Option<u64>, shuffle_activations: bool) {
+        self.scheduling.random = seed.map(|seed| {
+            event!(Level::INFO, seed, "using random scheduling");
+
+            Random {
+                seed,
+                rng: XorShift::new(seed),
+                shuffle_activations,
+            }
+        });
+    }
+
+    /// Get the seed of random scheduling, `None` if scheduling in FIFO order.
+    pub fn scheduling_seed(&self) -> Option<u64> {
+        self.scheduling.random.as_ref().map(|r| r.seed)
+    }
+
+    /// Take the next scheduled actor from the queue.
+    pub(super) fn next_scheduled(&mut self) -> Option<Index> {
+        let Some(random) = &mut self.scheduling.random else {
+            return self.queue.pop_front();
+        };
+
+        if self.queue.is_empty() {
+            return None;
+        }
+
+        let position = random.rng.below(self.queue.len());
+        self.queue.swap_remove_back(position)
+    }
+
+    /// Get the message limit for the next activation of an actor with `pending` messages.
+    pub(super) fn activation_limit(&mut self, budget: usize, pending: usize) -> usize {
+        let Some(random) = &mut self.scheduling.random else {
+            return budget;
+        };
+
+        if !random.shuffle_activations {
+            return budget;
+        }
+
+        let limit = budget.min(pending).max(1);
+        random.rng.below(limit) + 1
+    }
+}
+
+/// Small, fast, seedable random number generator.
+///
+/// Scheduling doesn't need good randomness, just a reproducible order.
+struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // Scramble the seed with splitmix64, so similar seeds give different sequences, and a
+        // seed of zero doesn't get stuck at zero
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        Self { state: z.max(1) }
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Get a random number in `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
@@ -0,0 +1,469 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use thiserror::Error;
+use thunderdome::Index;
+use tracing::{event, instrument, Level};
+
+use crate::container::{ActorContainer, AnyActorContainer, AnyQueue};
+use crate::runtime::record::{encode_bytes, Reader};
+use crate::runtime::{ActorEntry, ActorOptions, DecodeError, Id, Overflow, Recordable, Runtime};
+use crate::{Actor, Addr, InternalError};
+
+/// State that can be saved into a runtime snapshot, and loaded from it again.
+///
+/// Implement this for actors and their message types to opt them in to snapshots, with
+/// `Runtime::enable_snapshot`. Every `Recordable` type is also `Snapshot`.
+pub trait Snapshot: Sized + 'static {
+    /// Serialize the state, appending it to `buffer`.
+    fn save(&self, buffer: &mut Vec<u8>);
+
+    /// Deserialize state previously serialized with `save`.
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError>;
+}
+
+impl<T> Snapshot for T
+where
+    T: Recordable,
+{
+    fn save(&self, buffer: &mut Vec<u8>) {
+        self.encode(buffer);
+    }
+
+    fn load(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::decode(bytes)
+    }
+}
+
+/// Saved state of the snapshot-enabled actors in a runtime.
+#[derive(Debug, Default, Clone)]
+pub struct RuntimeSnapshot {
+    actors: Vec<ActorSnapshot>,
+}
+
+#[derive(Debug, Clone)]
+struct ActorSnapshot {
+    id: Id,
+    name: String,
+    type_name: String,
+    parent: Option<Id>,
+    options: ActorOptions,
+    state: Vec<u8>,
+    messages: Vec<Vec<u8>>,
+}
+
+/// Type-erased snapshot functions of an actor.
+#[derive(Clone, Copy)]
+pub(super) struct SnapshotFns {
+    type_name: &'static str,
+    save: fn(&dyn AnyActorContainer, &dyn AnyQueue) -> Option<Saved>,
+    load: fn(&ActorSnapshot) -> Result<Loaded, DecodeError>,
+}
+
+/// Saved actor state, and its saved pending messages.
+type Saved = (Vec<u8>, Vec<Vec<u8>>);
+
+type Loaded = (Box<dyn AnyActorContainer>, Box<dyn AnyQueue>);
+
+/// Actor types and names that have been snapshot-enabled.
+///
+/// Restoring uses these to recreate actors that were removed after the snapshot was taken.
+#[derive(Default)]
+pub(super) struct SnapshotTypes {
+    fns: HashMap<&'static str, SnapshotFns>,
+    names: HashSet<&'static str>,
+}
+
+impl Runtime {
+    /// Include an actor in snapshots of the runtime.
+    ///
+    /// Both the actor and its message type need to implement `Snapshot`.
+    #[instrument("Runtime::enable_snapshot", level = "debug", skip_all)]
+    pub fn enable_snapshot<A>(&mut self, addr: Addr<A>) -> Result<(), SnapshotError>
+    where
+        A: Actor + Snapshot,
+        A::Message: Snapshot,
+    {
+        let entry = self
+            .actors
+            .get_mut(addr.id().index)
+            .ok_or(SnapshotError::NotFound)?;
+
+        let fns = SnapshotFns {
+            type_name: std::any::type_name::<A>(),
+            save: save::<A>,
+            load: load::<A>,
+        };
+        entry.snapshot = Some(fns);
+
+        self.snapshot_types.fns.insert(fns.type_name, fns);
+        self.snapshot_types.names.insert(entry.name);
+
+        Ok(())
+    }
+
+    /// Save the state of every snapshot-enabled actor, including its pending messages.
+    ///
+    /// Actors that aren't snapshot-enabled, timers, and pending asks aren't included.
+    /// This fails if a snapshot-enabled actor is being processed, so it can't be taken from
+    /// inside an actor.
+    #[instrument("Runtime::snapshot", level = "debug", skip_all)]
+    pub fn snapshot(&self) -> Result<RuntimeSnapshot, SnapshotError> {
+        let mut actors = Vec::new();
+
+        for (index, entry) in &self.actors {
+            let Some(fns) = entry.snapshot else {
+                continue;
+            };
+
+            let (Some(container), Some(queue)) = (&entry.container, &entry.queue) else {
+                return Err(SnapshotError::Busy);
+            };
+            let (state, messages) =
+                (fns.save)(container.as_ref(), queue.as_ref()).ok_or(SnapshotError::WrongType)?;
+
+            actors.push(ActorSnapshot {
+                id: Id { index },
+                name: entry.name.to_string(),
+                type_name: fns.type_name.to_string(),
+                parent: entry.parent.map(|index| Id { index }),
+                options: entry.options,
+                state,
+                messages,
+            });
+        }
+
+        event!(Level::DEBUG, count = actors.len(), "saved snapshot");
+
+        Ok(RuntimeSnapshot { actors })
+    }
+
+    /// Restore the state of actors from a snapshot, replacing their state and pending messages.
+    ///
+    /// Ids are never changed by restoring, so senders and addresses stay valid. Actors still in
+    /// the runtime must be snapshot-enabled with the same type. If anything fails, nothing is
+    /// restored.
+    ///
+    /// Snapshot-enabled actors that aren't in the snapshot, because they were inserted after it
+    /// was taken, are removed, including their children.
+    /// Actors in the snapshot that have been removed since are recreated with the same `Id`, name,
+    /// parent and options. Their monitors, registrations and supervision aren't recreated.
+    /// Their type and name must have been snapshot-enabled before in this runtime, and their
+    /// parent must still exist, or be recreated too.
+    ///
+    /// Removed actors get their lifecycle hooks called as with `remove`, otherwise actors are
+    /// replaced and recreated without calling lifecycle hooks.
+    #[instrument("Runtime::restore", level = "debug", skip_all)]
+    pub fn restore(&mut self, snapshot: &RuntimeSnapshot) -> Result<(), SnapshotError> {
+        let included: HashSet<Index> = snapshot.actors.iter().map(|a| a.id.index).collect();
+
+        // Find actors inserted after the snapshot, and what goes away with them
+        let stale: Vec<Index> = self
+            .actors
+            .iter()
+            .filter(|(index, entry)| entry.snapshot.is_some() && !included.contains(index))
+            .map(|(index, _)| index)
+            .collect();
+        let removed = self.with_descendants(&stale);
+
+        for index in &removed {
+            if self.actors.get(*index).map(is_busy) == Some(true) {
+                return Err(SnapshotError::Busy);
+            }
+        }
+
+        // Load everything first, so a failure doesn't leave the runtime half-restored
+        let mut loaded = Vec::new();
+        for actor in &snapshot.actors {
+            let index = actor.id.index;
+
+            // Actors that are gone, or going, need to be recreated, which also needs their name
+            let (fns, name) = match self.actors.get(index) {
+                Some(entry) if !removed.contains(&index) => {
+                    let fns = entry.snapshot.ok_or(SnapshotError::NotEnabled)?;
+                    if fns.type_name != actor.type_name {
+                        return Err(SnapshotError::WrongType);
+                    }
+                    if is_busy(entry) {
+                        return Err(SnapshotError::Busy);
+                    }
+
+                    (fns, None)
+                }
+                _ => {
+                    let (fns, name) = self.check_recreate(actor, &included, &removed)?;
+                    (fns, Some(name))
+                }
+            };
+
+            loaded.push((actor, name, fns, (fns.load)(actor)?));
+        }
+
+        // Nothing can fail anymore, start changing the runtime
+        for index in stale {
+            let _ = self.remove(Id { index })?;
+        }
+
+        let mut recreated = Vec::new();
+        for (actor, name, fns, (container, queue)) in loaded {
+            let index = actor.id.index;
+
+            if let Some(name) = name {
+                let mut entry = ActorEntry::new(name, actor.parent.map(|id| id.index));
+                entry.type_name = Some(fns.type_name);
+                entry.options = actor.options;
+                entry.snapshot = Some(fns);
+                entry.container = Some(container);
+                entry.queue = Some(queue);
+
+                self.actors.insert_at(index, entry);
+                recreated.push(index);
+            } else if let Some(entry) = self.actors.get_mut(index) {
+                entry.container = Some(container);
+                entry.queue = Some(queue);
+            }
+
+            self.reschedule(index);
+        }
+
+        // Link recreated actors to their parents, now that all parents exist again
+        for index in recreated {
+            let Some(entry) = self.actors.get(index) else {
+                continue;
+            };
+            let name = entry.name;
+
+            if let Some(parent) = entry.parent.and_then(|i| self.actors.get_mut(i)) {
+                parent.children.push(index);
+            }
+
+            let id = Id { index };
+            self.observe(|o| o.inserted(id, name));
+        }
+
+        event!(
+            Level::DEBUG,
+            count = snapshot.actors.len(),
+            "restored snapshot"
+        );
+
+        Ok(())
+    }
+
+    /// Check an actor in a snapshot can be recreated, returning its functions and name.
+    fn check_recreate(
+        &self,
+        actor: &ActorSnapshot,
+        included: &HashSet<Index>,
+        removed: &HashSet<Index>,
+    ) -> Result<(SnapshotFns, &'static str), SnapshotError> {
+        let fns = self
+            .snapshot_types
+            .fns
+            .get(actor.type_name.as_str())
+            .copied()
+            .ok_or(SnapshotError::NotEnabled)?;
+        let name = self
+            .snapshot_types
+            .names
+            .get(actor.name.as_str())
+            .copied()
+            .ok_or(SnapshotError::NotEnabled)?;
+
+        // The slot may have been reused, which is only fine if that actor is being removed
+        if let Some(occupant) = self.actors.contains_slot(actor.id.index.slot()) {
+            if !removed.contains(&occupant) {
+                return Err(SnapshotError::Occupied);
+            }
+        }
+
+        if let Some(parent) = actor.parent {
+            let exists = self.actors.contains(parent.index) && !removed.contains(&parent.index);
+            if !exists && !included.contains(&parent.index) {
+                return Err(SnapshotError::NotFound);
+            }
+        }
+
+        Ok((fns, name))
+    }
+
+    /// Collect actors and all their descendants.
+    fn with_descendants(&self, roots: &[Index]) -> HashSet<Index> {
+        let mut found = HashSet::new();
+        let mut pending = roots.to_vec();
+
+        while let Some(index) = pending.pop() {
+            if !found.insert(index) {
+                continue;
+            }
+
+            if let Some(entry) = self.actors.get(index) {
+                pending.extend_from_slice(&entry.children);
+            }
+        }
+
+        found
+    }
+}
+
+fn is_busy(entry: &ActorEntry) -> bool {
+    entry.container.is_none() && entry.queue.is_some()
+}
+
+impl RuntimeSnapshot {
+    /// Serialize the snapshot, for example to send it over the network.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+
+        for actor in &self.actors {
+            actor.id.index.to_bits().encode(&mut buffer);
+            encode_bytes(actor.name.as_bytes(), &mut buffer);
+            encode_bytes(actor.type_name.as_bytes(), &mut buffer);
+            match actor.parent {
+                Some(parent) => {
+                    buffer.push(1);
+                    parent.index.to_bits().encode(&mut buffer);
+                }
+                None => buffer.push(0),
+            }
+            match actor.options.capacity {
+                Some(capacity) => {
+                    buffer.push(1);
+                    (capacity as u64).encode(&mut buffer);
+                }
+                None => buffer.push(0),
+            }
+            let overflow: u8 = match actor.options.overflow {
+                Overflow::Reject => 0,
+                Overflow::DropOldest => 1,
+                Overflow::DropNewest => 2,
+            };
+            buffer.push(overflow);
+            encode_bytes(&actor.state, &mut buffer);
+            (actor.messages.len() as u64).encode(&mut buffer);
+            for message in &actor.messages {
+                encode_bytes(message, &mut buffer);
+            }
+        }
+
+        buffer
+    }
+
+    /// Deserialize a snapshot previously serialized with `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader::new(bytes);
+        let mut actors = Vec::new();
+
+        while !reader.is_empty() {
+            let id = reader.id()?;
+            let name = reader.string()?;
+            let type_name = reader.string()?;
+            let parent = match reader.take(1)?[0] {
+                0 => None,
+                _ => Some(reader.id()?),
+            };
+            let capacity = match reader.take(1)?[0] {
+                0 => None,
+                _ => {
+                    let capacity = u64::decode(reader.take(8)?)?;
+                    Some(usize::try_from(capacity).map_err(|_| DecodeError)?)
+                }
+            };
+            let overflow = match reader.take(1)?[0] {
+                0 => Overflow::Reject,
+                1 => Overflow::DropOldest,
+                2 => Overflow::DropNewest,
+                _ => return Err(DecodeError.into()),
+            };
+            let options = ActorOptions { capacity, overflow };
+            let state = reader.bytes()?.to_vec();
+
+            let count = u64::decode(reader.take(8)?)?;
+            let mut messages = Vec::new();
+            for _ in 0..count {
+                messages.push(reader.bytes()?.to_vec());
+            }
+
+            actors.push(ActorSnapshot {
+                id,
+                name,
+                type_name,
+                parent,
+                options,
+                state,
+                messages,
+            });
+        }
+
+        Ok(Self { actors })
+    }
+}
+
+fn save<A>(container: &dyn AnyActorContainer, queue: &dyn AnyQueue) -> Option<Saved>
+where
+    A: Actor + Snapshot,
+    A::Message: Snapshot,
+{
+    let container = container.as_any().downcast_ref::<ActorContainer<A>>()?;
+    let queue = queue.as_any().downcast_ref::<VecDeque<A::Message>>()?;
+
+    let mut state = Vec::new();
+    container.actor().save(&mut state);
+
+    let messages = queue
+        .iter()
+        .map(|message| {
+            let mut buffer = Vec::new();
+            message.save(&mut buffer);
+            buffer
+        })
+        .collect();
+
+    Some((state, messages))
+}
+
+fn load<A>(snapshot: &ActorSnapshot) -> Result<Loaded, DecodeError>
+where
+    A: Actor + Snapshot,
+    A::Message: Snapshot,
+{
+    let actor = A::load(&snapshot.state)?;
+
+    let mut queue = VecDeque::with_capacity(snapshot.messages.len());
+    for message in &snapshot.messages {
+        queue.push_back(A::Message::load(message)?);
+    }
+
+    Ok((Box::new(ActorContainer::new(actor)), Box::new(queue)))
+}
+
+/// Failed to snapshot or restore a runtime.
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    /// No actor found for id.
+    #[error("no actor found for id")]
+    NotFound,
+
+    /// Actor isn't snapshot-enabled.
+    #[error("actor isn't snapshot-enabled")]
+    NotEnabled,
+
+    /// Actor's type doesn't match the snapshot.
+    #[error("actor's type doesn't match snapshot")]
+    WrongType,
+
+    /// Actor is being processed, and can't be saved or replaced.
+    #[error("actor is being processed")]
+    Busy,
+
+    /// Another actor is using the `Id` of an actor that needs to be recreated.
+    #[error("id of removed actor is in use by another actor")]
+    Occupied,
+
+    /// Snapshot data couldn't be decoded.
+    #[error("failed to decode snapshot")]
+    Decode(#[from] DecodeError),
+
+    /// Internal error in stewart.
+    #[error("internal error")]
+    Internal(#[from] InternalError),
+}
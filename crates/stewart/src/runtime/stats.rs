@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use thunderdome::Index;
+
+use crate::runtime::{ActorEntry, Id, Runtime};
+
+/// Snapshot of a live actor in a runtime.
+#[derive(Debug, Clone)]
+pub struct ActorInfo {
+    /// Id of the actor.
+    pub id: Id,
+    /// Name the actor was inserted with.
+    pub name: &'static str,
+    /// Name of the actor's type, `None` if the actor hasn't been started yet.
+    pub type_name: Option<&'static str>,
+    /// Amount of messages pending in the actor's queue.
+    pub queue_len: usize,
+    /// Parent of the actor, if any.
+    pub parent: Option<Id>,
+    /// Counters collected for the actor.
+    pub stats: ActorStats,
+}
+
+/// Counters collected for an actor, since it was inserted or stats were last reset.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ActorStats {
+    /// Messages successfully queued for the actor.
+    pub received: u64,
+    /// Messages taken from the queue and passed to `Actor::handle`.
+    pub processed: u64,
+    /// Total time spent processing messages.
+    pub handle_time: Duration,
+    /// Times the actor failed, by returning an error or panicking.
+    pub errors: u64,
+}
+
+impl Runtime {
+    /// Iterate over all live actors, in no particular order.
+    pub fn actors(&self) -> impl Iterator<Item = ActorInfo> + '_ {
+        self.actors
+            .iter()
+            .map(|(index, entry)| actor_info(index, entry))
+    }
+
+    /// Get a snapshot of a live actor.
+    pub fn actor_info(&self, id: Id) -> Option<ActorInfo> {
+        let entry = self.actors.get(id.index)?;
+        Some(actor_info(id.index, entry))
+    }
+
+    /// Reset the counters of all actors.
+    pub fn reset_stats(&mut self) {
+        for (_, entry) in &mut self.actors {
+            entry.stats = ActorStats::default();
+        }
+    }
+
+    /// Record the result of an actor activation.
+    pub(super) fn record_activation(&mut self, index: Index, processed: usize, time: Duration) {
+        if let Some(entry) = self.actors.get_mut(index) {
+            entry.stats.processed += processed as u64;
+            entry.stats.handle_time += time;
+        }
+    }
+
+    /// Record an actor failing.
+    pub(super) fn record_error(&mut self, index: Index) {
+        if let Some(entry) = self.actors.get_mut(index) {
+            entry.stats.errors += 1;
+        }
+    }
+}
+
+fn actor_info(index: Index, entry: &ActorEntry) -> ActorInfo {
+    ActorInfo {
+        id: Id { index },
+        name: entry.name,
+        type_name: entry.type_name,
+        queue_len: entry.queue.as_ref().map(|q| q.len()).unwrap_or(0),
+        parent: entry.parent.map(|index| Id { index }),
+        stats: entry.stats,
+    }
+}
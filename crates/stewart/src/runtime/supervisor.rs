@@ -0,0 +1,376 @@
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context as _, Error};
+use thunderdome::Index;
+use tracing::{event, instrument, Level};
+
+use crate::container::{ActorContainer, AnyActorContainer};
+use crate::runtime::unwind::{catch_unwind, catch_unwind_hook};
+use crate::runtime::{Id, Runtime, StopReason};
+use crate::{Actor, ActorError, Addr, Context, InternalError};
+
+/// Strategy a supervisor uses to restart its children when one fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Only restart the child that failed.
+    #[default]
+    OneForOne,
+    /// Restart all children when any child fails.
+    OneForAll,
+    /// Restart the child that failed, and all children inserted after it.
+    RestForOne,
+}
+
+/// Limit on how often a supervisor may restart children, before giving up.
+///
+/// If more than `max_restarts` restarts happen within `period`, the supervisor stops itself and
+/// all its children.
+/// If the supervisor is itself supervised, this is handled as a failure of the supervisor.
+#[derive(Debug, Clone, Copy)]
+pub struct Intensity {
+    /// Maximum amount of restarts allowed within `period`.
+    pub max_restarts: usize,
+    /// Time window restarts are counted in.
+    pub period: Duration,
+}
+
+impl Default for Intensity {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            period: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Configuration of a supervisor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SupervisorOptions {
+    /// Which children to restart when a child fails.
+    pub strategy: Strategy,
+    /// How often children may be restarted before the supervisor gives up.
+    pub intensity: Intensity,
+}
+
+/// Supervision tracking of a supervisor entry.
+pub(super) struct SupervisorState {
+    options: SupervisorOptions,
+    restarts: VecDeque<Instant>,
+}
+
+impl SupervisorState {
+    fn new(options: SupervisorOptions) -> Self {
+        Self {
+            options,
+            restarts: VecDeque::new(),
+        }
+    }
+
+    /// Record a restart, returning false if this exceeds the restart intensity.
+    fn record_restart(&mut self, now: Instant) -> bool {
+        let intensity = self.options.intensity;
+
+        // Forget restarts that are outside of the period
+        while let Some(restart) = self.restarts.front() {
+            if now.duration_since(*restart) <= intensity.period {
+                break;
+            }
+
+            self.restarts.pop_front();
+        }
+
+        self.restarts.push_back(now);
+        self.restarts.len() <= intensity.max_restarts
+    }
+}
+
+/// Supervision tracking of an entry supervised by its parent.
+pub(super) struct Supervised {
+    /// Factory to re-create the actor, `None` for supervisors.
+    factory: Option<Box<Factory>>,
+}
+
+type Factory = dyn FnMut(&mut Runtime, Id) -> Result<Box<dyn AnyActorContainer>, Error>;
+
+/// Placeholder actor of supervisor entries, supervisors can't receive messages.
+struct Supervisor;
+
+impl Actor for Supervisor {
+    type Message = Infallible;
+
+    fn handle(
+        &mut self,
+        _ctx: &mut Context,
+        message: Infallible,
+    ) -> Result<ControlFlow<()>, ActorError> {
+        match message {}
+    }
+}
+
+impl Runtime {
+    /// Insert a supervisor into the runtime.
+    ///
+    /// A supervisor doesn't process messages itself, instead it restarts its supervised children
+    /// when they fail with an `ActorError`, using the strategy and intensity in `options`.
+    ///
+    /// If `parent` is given, the new supervisor is inserted as a child of `parent`.
+    /// If `parent` is a supervisor, the new supervisor is supervised by it, and restarting the
+    /// new supervisor restarts all its children.
+    #[instrument("Runtime::insert_supervisor", level = "debug", skip_all)]
+    pub fn insert_supervisor(
+        &mut self,
+        parent: Option<Id>,
+        name: &'static str,
+        options: SupervisorOptions,
+    ) -> Result<Id, InternalError> {
+        let id = match parent {
+            Some(parent) => self.create_child(parent, name)?,
+            None => self.create(name)?,
+        };
+        self.start(id, Supervisor)?;
+
+        let parent_is_supervisor = parent
+            .and_then(|p| self.actors.get(p.index))
+            .map(|e| e.supervisor.is_some())
+            .unwrap_or(false);
+
+        let entry = self
+            .actors
+            .get_mut(id.index)
+            .context("failed to find actor")?;
+        entry.supervisor = Some(SupervisorState::new(options));
+
+        if parent_is_supervisor {
+            entry.supervised = Some(Supervised { factory: None });
+        }
+
+        Ok(id)
+    }
+
+    /// Insert an actor supervised by `supervisor`, as a child of it.
+    ///
+    /// The actor is created by calling `factory`, which will be called again to re-create the
+    /// actor when the supervisor restarts it.
+    /// The `Id` of the actor stays the same between restarts, but pending messages are dropped,
+    /// and children of the actor are removed.
+    #[instrument("Runtime::insert_supervised", level = "debug", skip_all)]
+    pub fn insert_supervised<A, F>(
+        &mut self,
+        supervisor: Id,
+        name: &'static str,
+        mut factory: F,
+    ) -> Result<Addr<A>, Error>
+    where
+        A: Actor,
+        F: FnMut(&mut Runtime, Id) -> Result<A, Error> + 'static,
+    {
+        let supervisor_entry = self
+            .actors
+            .get(supervisor.index)
+            .context("failed to find supervisor")?;
+        if supervisor_entry.supervisor.is_none() {
+            bail!("actor is not a supervisor");
+        }
+
+        let id = self.create_child(supervisor, name)?;
+
+        let mut factory: Box<Factory> = Box::new(move |rt, id| {
+            let actor = factory(rt, id)?;
+            let container: Box<dyn AnyActorContainer> = Box::new(ActorContainer::new(actor));
+            Ok(container)
+        });
+
+        // Create the first instance of the actor
+        let container = match factory(self, id) {
+            Ok(container) => container,
+            Err(error) => {
+                self.remove(id)??;
+                return Err(error);
+            }
+        };
+
+        // Mark as supervised before starting, so a failing `started` hook is restarted
+        let entry = self
+            .actors
+            .get_mut(id.index)
+            .context("failed to find actor")?;
+        entry.queue = Some(ActorContainer::<A>::create_queue());
+        entry.type_name = Some(std::any::type_name::<A>());
+        entry.supervised = Some(Supervised {
+            factory: Some(factory),
+        });
+        self.start_container(id.index, container)?;
+
+        Ok(Addr::new(id))
+    }
+
+    /// Handle an actor failing, restarting it if it's supervised or removing it if not.
+    pub(super) fn handle_failure(&mut self, index: Index) -> Result<(), Error> {
+        let now = self.now();
+        let entry = self.actors.get(index).context("failed to find actor")?;
+        let name = entry.name;
+
+        // Unsupervised actors are just removed
+        let (Some(_), Some(supervisor)) = (&entry.supervised, entry.parent) else {
+            self.remove_with_reason(Id { index }, StopReason::Error)??;
+            return Ok(());
+        };
+
+        let supervisor_entry = self
+            .actors
+            .get_mut(supervisor)
+            .context("failed to find supervisor")?;
+        let supervisor_name = supervisor_entry.name;
+        let state = supervisor_entry
+            .supervisor
+            .as_mut()
+            .context("actor is not a supervisor")?;
+
+        // Check if we're still allowed to restart
+        if !state.record_restart(now) {
+            event!(
+                Level::ERROR,
+                supervisor = supervisor_name,
+                "restart intensity exceeded, stopping supervisor",
+            );
+            return self.handle_failure(supervisor);
+        }
+
+        // Find which children need to be restarted
+        let strategy = state.options.strategy;
+        let children = self.supervised_children(supervisor);
+        let targets = match strategy {
+            Strategy::OneForOne => vec![index],
+            Strategy::OneForAll => children,
+            Strategy::RestForOne => {
+                let position = children.iter().position(|c| *c == index);
+                let position = position.context("failed to find child in supervisor")?;
+                children[position..].to_vec()
+            }
+        };
+
+        event!(
+            Level::WARN,
+            name,
+            supervisor = supervisor_name,
+            "restarting after failure"
+        );
+        for target in targets {
+            self.restart(target)?;
+        }
+
+        Ok(())
+    }
+
+    fn supervised_children(&self, supervisor: Index) -> Vec<Index> {
+        let Some(entry) = self.actors.get(supervisor) else {
+            return Vec::new();
+        };
+
+        entry
+            .children
+            .iter()
+            .cloned()
+            .filter(|i| self.actors.get(*i).map(|e| e.supervised.is_some()) == Some(true))
+            .collect()
+    }
+
+    fn restart(&mut self, index: Index) -> Result<(), Error> {
+        // A previous failure handling may have already removed this actor
+        let Some(entry) = self.actors.get_mut(index) else {
+            return Ok(());
+        };
+
+        // Restarting a supervisor restarts all its children
+        if let Some(state) = &mut entry.supervisor {
+            state.restarts.clear();
+
+            for child in self.supervised_children(index) {
+                self.restart(child)?;
+            }
+
+            return Ok(());
+        }
+
+        // If the actor is borrowed, for example because it's a running sibling of the actor that
+        // failed, restart it once it's returned, so the returned instance can't replace the new one
+        if entry.container.is_none() && entry.queue.is_some() {
+            entry.restarting = true;
+            return Ok(());
+        }
+
+        // Let the old actor instance know it's stopping
+        let old = entry.container.take();
+        if let Some(mut old) = old {
+            let id = Id { index };
+            catch_unwind_hook("stopping", || old.stopping(self, id, StopReason::Restart));
+            if !self.unborrow(index, old) || self.finish_removal(index)? {
+                return Ok(());
+            }
+        }
+
+        // Children of the old actor instance don't survive the restart
+        let entry = self.actors.get_mut(index).context("failed to find actor")?;
+        let children = std::mem::take(&mut entry.children);
+        for child in children.into_iter().rev() {
+            let _ = self.remove(Id { index: child })?;
+        }
+
+        // Re-create the actor using its factory
+        let entry = self.actors.get_mut(index).context("failed to find actor")?;
+        let supervised = entry
+            .supervised
+            .as_mut()
+            .context("actor is not supervised")?;
+        let mut factory = supervised
+            .factory
+            .take()
+            .context("expected factory not available")?;
+        event!(Level::DEBUG, name = entry.name, "restarting actor");
+
+        let result = catch_unwind(|| factory(self, Id { index }));
+        let entry = self.actors.get_mut(index).context("failed to find actor")?;
+        if let Some(supervised) = &mut entry.supervised {
+            supervised.factory = Some(factory);
+        }
+
+        // Restarts requested while the old instance was stopping are covered by this one
+        entry.restarting = false;
+
+        // The old actor instance has fully stopped now
+        if let Some(mut old) = entry.container.take() {
+            catch_unwind_hook("stopped", || old.stopped());
+        }
+
+        match result {
+            Ok(container) => {
+                // Replace the old actor, dropping its pending messages
+                self.drop_pending(index)?;
+                self.wake_capacity_watchers(index);
+                self.start_container(index, container)?;
+            }
+            Err(error) => {
+                event!(Level::ERROR, "failed to restart actor:\n{}", error);
+                self.handle_failure(index)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restart an actor that was restarted while borrowed, returning true if it was.
+    pub(super) fn finish_restart(&mut self, index: Index) -> Result<bool, Error> {
+        let Some(entry) = self.actors.get_mut(index) else {
+            return Ok(false);
+        };
+        if !std::mem::take(&mut entry.restarting) {
+            return Ok(false);
+        }
+
+        self.restart(index)?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use tracing::{event, instrument, Level};
+
+use crate::runtime::Runtime;
+use crate::sender::Sender;
+use crate::InternalError;
+
+/// Shortest period of a repeating timer.
+const MIN_PERIOD: Duration = Duration::from_millis(1);
+
+/// Handle to a timer, for cancelling it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle {
+    id: u64,
+}
+
+/// Pending timers of a runtime, ordered by deadline.
+#[derive(Default)]
+pub(super) struct Timers {
+    entries: BTreeMap<(Instant, u64), TimerEntry>,
+    /// Current deadline of every pending timer, to find its entry when cancelling.
+    deadlines: HashMap<u64, Instant>,
+    next_id: u64,
+}
+
+struct TimerEntry {
+    timer: Box<dyn AnyTimer>,
+    /// Set if this timer repeats.
+    period: Option<Duration>,
+}
+
+impl Timers {
+    fn insert(&mut self, id: u64, deadline: Instant, entry: TimerEntry) {
+        self.entries.insert((deadline, id), entry);
+        self.deadlines.insert(id, deadline);
+    }
+
+    fn remove(&mut self, id: u64) -> Option<TimerEntry> {
+        let deadline = self.deadlines.remove(&id)?;
+        self.entries.remove(&(deadline, id))
+    }
+
+    /// Take all timers that are due at `now`.
+    fn take_due(&mut self, now: Instant) -> Vec<(u64, Instant, TimerEntry)> {
+        let mut due = Vec::new();
+
+        while let Some(entry) = self.entries.first_entry() {
+            let (deadline, id) = *entry.key();
+            if deadline > now {
+                break;
+            }
+
+            let entry = entry.remove();
+            self.deadlines.remove(&id);
+            due.push((id, deadline, entry));
+        }
+
+        due
+    }
+}
+
+impl Runtime {
+    /// Send `message` to `sender` after `delay` has passed.
+    #[instrument("Runtime::send_after", level = "debug", skip_all)]
+    pub fn send_after<M>(&mut self, sender: Sender<M>, delay: Duration, message: M) -> TimerHandle
+    where
+        M: 'static,
+    {
+        let timer = Once {
+            sender,
+            message: Some(message),
+        };
+        self.add_timer(self.now() + delay, None, Box::new(timer))
+    }
+
+    /// Send a clone of `message` to `sender` every `period`, starting one `period` from now.
+    ///
+    /// If the runtime falls behind, missed deliveries are skipped rather than sent in a burst.
+    /// The timer stops by itself if sending fails, for example because the target was removed.
+    /// Periods shorter than one millisecond, including zero, are raised to one millisecond, so
+    /// the timer can't keep the runtime busy forever.
+    #[instrument("Runtime::send_interval", level = "debug", skip_all)]
+    pub fn send_interval<M>(
+        &mut self,
+        sender: Sender<M>,
+        period: Duration,
+        message: M,
+    ) -> TimerHandle
+    where
+        M: Clone + 'static,
+    {
+        let period = period.max(MIN_PERIOD);
+        let timer = Interval { sender, message };
+        self.add_timer(self.now() + period, Some(period), Box::new(timer))
+    }
+
+    /// Cancel a timer, returning false if it had already finished or was cancelled.
+    #[instrument("Runtime::cancel_timer", level = "debug", skip_all)]
+    pub fn cancel_timer(&mut self, handle: TimerHandle) -> bool {
+        self.timers.remove(handle.id).is_some()
+    }
+
+    /// Get the earliest deadline of any pending timer.
+    ///
+    /// Event loops can use this to know how long they can sleep before calling `process` again.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers
+            .entries
+            .keys()
+            .next()
+            .map(|(deadline, _)| *deadline)
+    }
+
+    pub(crate) fn add_timer(
+        &mut self,
+        deadline: Instant,
+        period: Option<Duration>,
+        timer: Box<dyn AnyTimer>,
+    ) -> TimerHandle {
+        let id = self.timers.next_id;
+        self.timers.next_id += 1;
+
+        let entry = TimerEntry { timer, period };
+        self.timers.insert(id, deadline, entry);
+
+        TimerHandle { id }
+    }
+
+    pub(super) fn fire_timers(&mut self, now: Instant) -> Result<(), InternalError> {
+        for (id, deadline, mut entry) in self.timers.take_due(now) {
+            event!(Level::TRACE, "firing timer");
+            let keep = self.as_runtime(|rt| entry.timer.fire(rt))?;
+
+            // Re-schedule repeating timers, skipping any deliveries we've missed
+            let Some(period) = entry.period else {
+                continue;
+            };
+            if !keep {
+                continue;
+            }
+
+            let mut next = deadline + period;
+            if next <= now {
+                next = now + period;
+            }
+            self.timers.insert(id, next, entry);
+        }
+
+        Ok(())
+    }
+}
+
+/// Type-erased action to run when a timer is due.
+pub(crate) trait AnyTimer {
+    /// Run the timer, returning false if a repeating timer should stop.
+    fn fire(&mut self, rt: &mut Runtime) -> Result<bool, InternalError>;
+}
+
+struct Once<M> {
+    sender: Sender<M>,
+    message: Option<M>,
+}
+
+impl<M> AnyTimer for Once<M>
+where
+    M: 'static,
+{
+    fn fire(&mut self, rt: &mut Runtime) -> Result<bool, InternalError> {
+        if let Some(message) = self.message.take() {
+            if let Err(error) = self.sender.send(rt, message)? {
+                event!(Level::DEBUG, ?error, "failed to send timer message");
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+struct Interval<M> {
+    sender: Sender<M>,
+    message: M,
+}
+
+impl<M> AnyTimer for Interval<M>
+where
+    M: Clone + 'static,
+{
+    fn fire(&mut self, rt: &mut Runtime) -> Result<bool, InternalError> {
+        if let Err(error) = self.sender.send(rt, self.message.clone())? {
+            event!(
+                Level::DEBUG,
+                ?error,
+                "failed to send timer message, stopping"
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,49 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use anyhow::{anyhow, Error};
+use tracing::{event, Level};
+
+/// Call `f`, turning a panic into an error.
+///
+/// Actor state may be inconsistent after a panic, so the actor should be treated as failed.
+pub(super) fn catch_unwind<T, E, F>(f: F) -> Result<T, E>
+where
+    E: From<Error>,
+    F: FnOnce() -> Result<T, E>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let error = anyhow!("actor panicked: {}", panic_message(&payload));
+            Err(error.into())
+        }
+    }
+}
+
+/// Call a lifecycle hook that can't fail, logging if it panics.
+pub(super) fn catch_unwind_hook<F>(hook: &'static str, f: F)
+where
+    F: FnOnce(),
+{
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(f)) {
+        event!(
+            Level::ERROR,
+            hook,
+            message = panic_message(&payload),
+            "actor panicked in lifecycle hook",
+        );
+    }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = payload.downcast_ref::<&'static str>() {
+        return message;
+    }
+
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message;
+    }
+
+    "unknown panic payload"
+}
@@ -0,0 +1,175 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use thiserror::Error;
+
+use crate::runtime::AnyTimer;
+use crate::{sender::Sender, InternalError, Runtime, SendError, TimerHandle};
+
+/// Reply to an ask, or why no reply arrived.
+pub type AskResult<R> = Result<R, AskError>;
+
+impl<M> Sender<M>
+where
+    M: 'static,
+{
+    /// Send a request, expecting exactly one reply.
+    ///
+    /// `build` is given a one-shot `Sender<R>` to put in the request, which the target uses to
+    /// reply.
+    /// The reply, or an `AskError` if no reply arrives, is sent to `reply_to`.
+    /// Exactly one message is sent to `reply_to` for every successful ask.
+    ///
+    /// To tell apart replies of multiple asks, map `reply_to` with a key identifying the request.
+    pub fn ask<R, F>(
+        &self,
+        rt: &mut Runtime,
+        timeout: Duration,
+        reply_to: Sender<AskResult<R>>,
+        build: F,
+    ) -> Result<Result<(), SendError>, InternalError>
+    where
+        R: 'static,
+        F: FnOnce(Sender<R>) -> M,
+    {
+        let shared = Rc::new(RefCell::new(AskShared {
+            reply_to: Some(reply_to),
+            dropped: false,
+            timer: None,
+        }));
+
+        let reply = create_reply(&shared);
+        let request = build(reply);
+
+        // If the request can't be sent, the caller gets the error, not `reply_to`
+        let result = self.send(rt, request)?;
+        if result.is_ok() {
+            let timer = AskTimeout {
+                shared: shared.clone(),
+            };
+            let handle = rt.add_timer(rt.now() + timeout, None, Box::new(timer));
+            shared.borrow_mut().timer = Some(handle);
+            rt.add_ask(Box::new(shared));
+        }
+
+        Ok(result)
+    }
+}
+
+fn create_reply<R>(shared: &Rc<RefCell<AskShared<R>>>) -> Sender<R>
+where
+    R: 'static,
+{
+    let guard = ReplyGuard {
+        shared: shared.clone(),
+    };
+
+    Sender::from_fn(move |rt, message| {
+        // Only the first reply is passed along, after that the ask is done
+        let Some(reply_to) = finish(&guard.shared, rt) else {
+            return Ok(Err(SendError::NotFound));
+        };
+
+        reply_to.send(rt, Ok(message))
+    })
+}
+
+/// Failed to receive a reply to an ask.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AskError {
+    /// No reply arrived before the timeout.
+    #[error("no reply before timeout")]
+    Timeout,
+
+    /// The reply sender was dropped without replying, for example because the target was removed.
+    #[error("target gone without replying")]
+    Gone,
+}
+
+struct AskShared<R> {
+    /// Where to send the result, `None` once the ask is done.
+    reply_to: Option<Sender<AskResult<R>>>,
+    /// Set when all reply senders have been dropped.
+    dropped: bool,
+    /// Timeout timer, to cancel when the ask is done early.
+    timer: Option<TimerHandle>,
+}
+
+/// Mark an ask as done, returning where to send the result, or `None` if it was already done.
+fn finish<R>(shared: &RefCell<AskShared<R>>, rt: &mut Runtime) -> Option<Sender<AskResult<R>>> {
+    let mut shared = shared.borrow_mut();
+
+    if let Some(timer) = shared.timer.take() {
+        rt.cancel_timer(timer);
+    }
+
+    shared.reply_to.take()
+}
+
+/// Fail an ask, unless it's already done.
+fn fail<R>(
+    shared: &RefCell<AskShared<R>>,
+    rt: &mut Runtime,
+    error: AskError,
+) -> Result<(), InternalError>
+where
+    R: 'static,
+{
+    if let Some(reply_to) = finish(shared, rt) {
+        // If the asking actor is gone, there's nobody to tell
+        let _ = reply_to.send(rt, Err(error))?;
+    }
+
+    Ok(())
+}
+
+/// Held by the reply sender, to detect when it's dropped.
+struct ReplyGuard<R> {
+    shared: Rc<RefCell<AskShared<R>>>,
+}
+
+impl<R> Drop for ReplyGuard<R> {
+    fn drop(&mut self) {
+        self.shared.borrow_mut().dropped = true;
+    }
+}
+
+/// Type-erased ask that hasn't been replied to yet, tracked by the runtime.
+pub(crate) trait PendingAsk {
+    /// Check if the ask is done, failing it if the reply sender was dropped.
+    ///
+    /// Returns true if the ask is done, and doesn't need to be tracked anymore.
+    fn poll(&self, rt: &mut Runtime) -> Result<bool, InternalError>;
+}
+
+impl<R> PendingAsk for Rc<RefCell<AskShared<R>>>
+where
+    R: 'static,
+{
+    fn poll(&self, rt: &mut Runtime) -> Result<bool, InternalError> {
+        let shared = self.borrow();
+        if shared.reply_to.is_none() {
+            return Ok(true);
+        }
+        if !shared.dropped {
+            return Ok(false);
+        }
+        drop(shared);
+
+        fail(self, rt, AskError::Gone)?;
+        Ok(true)
+    }
+}
+
+struct AskTimeout<R> {
+    shared: Rc<RefCell<AskShared<R>>>,
+}
+
+impl<R> AnyTimer for AskTimeout<R>
+where
+    R: 'static,
+{
+    fn fire(&mut self, rt: &mut Runtime) -> Result<bool, InternalError> {
+        fail(&self.shared, rt, AskError::Timeout)?;
+        Ok(false)
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::{Rc, Weak},
+};
+
+use crate::{sender::Sender, Id, SendError};
+
+/// Message sent to an actor when one of its mailboxes receives a message.
+///
+/// Actors that receive through multiple mailboxes typically use this as their `Message` type,
+/// and check all their mailboxes when handling it.
+pub struct Signal;
+
+/// Typed inbox owned by an actor, that signals the actor when it receives a message.
+///
+/// This lets one actor receive multiple different message types, without having to combine them
+/// into one `Message` type.
+/// An actor can own as many mailboxes as it needs, all signalling the same actor.
+/// Create one with `Runtime::mailbox` or `Context::mailbox`.
+///
+/// Only the `Signal` goes through `Runtime::send`, messages in the mailbox itself don't.
+/// They don't count towards the owner's capacity or `received` statistics, aren't recorded or
+/// included in snapshots, and aren't sent to the dead letter sender if the mailbox is gone.
+pub struct Mailbox<T> {
+    owner: Id,
+    queue: Rc<RefCell<VecDeque<T>>>,
+}
+
+impl<T> Mailbox<T>
+where
+    T: 'static,
+{
+    pub(crate) fn new(owner: Id) -> Self {
+        Self {
+            owner,
+            queue: Rc::new(RefCell::new(VecDeque::new())),
+        }
+    }
+
+    /// Get the `Id` of the actor owning this mailbox.
+    pub fn owner(&self) -> Id {
+        self.owner
+    }
+
+    /// Create a sender that sends messages to this mailbox.
+    ///
+    /// If the mailbox has been dropped, sending will fail with `SendError::NotFound`.
+    /// If the owner can't be signalled, the message is taken out of the mailbox again, and
+    /// sending fails with the error of the signal.
+    pub fn sender(&self) -> Sender<T> {
+        let queue = Rc::downgrade(&self.queue);
+        let signal = Sender::new(self.owner);
+
+        Sender::from_fn(move |rt, message| {
+            if !try_push(&queue, message) {
+                return Ok(Err(SendError::NotFound));
+            }
+
+            let result = signal.send(rt, Signal);
+            if !matches!(result, Ok(Ok(()))) {
+                try_pop_back(&queue);
+            }
+
+            result
+        })
+    }
+
+    /// Receive the next message in the mailbox, if any are available.
+    pub fn recv(&self) -> Option<T> {
+        self.queue.borrow_mut().pop_front()
+    }
+}
+
+fn try_push<T>(queue: &Weak<RefCell<VecDeque<T>>>, message: T) -> bool {
+    let Some(queue) = queue.upgrade() else {
+        return false;
+    };
+
+    queue.borrow_mut().push_back(message);
+    true
+}
+
+fn try_pop_back<T>(queue: &Weak<RefCell<VecDeque<T>>>) {
+    if let Some(queue) = queue.upgrade() {
+        queue.borrow_mut().pop_back();
+    }
+}
@@ -1,5 +1,14 @@
 //! Message sending abstractions.
 
+mod ask;
+mod mailbox;
+#[allow(clippy::module_inception)]
 mod sender;
 
-pub use self::sender::Sender;
+pub(crate) use self::ask::PendingAsk;
+
+pub use self::{
+    ask::{AskError, AskResult},
+    mailbox::{Mailbox, Signal},
+    sender::Sender,
+};
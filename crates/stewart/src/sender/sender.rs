@@ -38,6 +38,18 @@ where
         }
     }
 
+    /// Create a sender that calls a function to send messages.
+    pub(crate) fn from_fn<F>(apply: F) -> Self
+    where
+        F: Fn(&mut Runtime, M) -> Result<Result<(), SendError>, InternalError> + 'static,
+    {
+        Self {
+            kind: SenderKind::Map {
+                apply: Rc::new(apply),
+            },
+        }
+    }
+
     /// Wrap the sender in a mapping sender.
     ///
     /// This lets you translate between message types quick and cheap.